@@ -2,7 +2,7 @@ use base64::Engine;
 use hmac::{Hmac, Mac};
 use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use rand::Rng;
-use sha1::Sha1;
+use sha1::{Digest, Sha1};
 use std::collections::BTreeMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -13,7 +13,7 @@ const OAUTH_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
     .remove(b'_')
     .remove(b'~');
 
-fn percent_encode(s: &str) -> String {
+pub(crate) fn percent_encode(s: &str) -> String {
     utf8_percent_encode(s, OAUTH_ENCODE_SET).to_string()
 }
 
@@ -32,11 +32,15 @@ impl Auth {
     /// Apply authentication to a request builder.
     /// For PersonalToken, adds an Authorization header.
     /// For OAuth, computes the HMAC-SHA1 signature and adds the Authorization header.
+    ///
+    /// `params` are the query and form-body parameters of the request, which
+    /// OAuth 1.0a folds into the signature. JSON bodies are not signed.
     pub fn apply(
         &self,
         builder: reqwest::RequestBuilder,
         method: &str,
         url: &str,
+        params: &[(&str, String)],
     ) -> reqwest::RequestBuilder {
         match self {
             Auth::PersonalToken(token) => {
@@ -48,11 +52,14 @@ impl Auth {
                 token,
                 token_secret,
             } => {
+                let params: Vec<(&str, &str)> =
+                    params.iter().map(|(k, v)| (*k, v.as_str())).collect();
                 let header = build_oauth_header(
                     consumer_key,
                     consumer_secret,
-                    token,
+                    Some(token),
                     token_secret,
+                    &params,
                     method,
                     url,
                 );
@@ -60,13 +67,33 @@ impl Auth {
             }
         }
     }
+
+    /// A digest identifying whose credentials these are, for keeping cached
+    /// responses of different users apart without storing the secrets.
+    pub fn cache_identity(&self) -> String {
+        let identity = match self {
+            Auth::PersonalToken(token) => format!("token:{token}"),
+            Auth::OAuth {
+                consumer_key,
+                token,
+                ..
+            } => format!("oauth:{consumer_key}:{token}"),
+        };
+        hex_encode(&Sha1::digest(identity.as_bytes()))
+    }
 }
 
-fn build_oauth_header(
+/// Build a signed OAuth 1.0a `Authorization` header (HMAC-SHA1).
+///
+/// `token` is `None` only while fetching a request token. Any `extra_params`
+/// are included in the signature base string, and those prefixed with
+/// `oauth_` (e.g. `oauth_callback`, `oauth_verifier`) are also sent in the header.
+pub(crate) fn build_oauth_header(
     consumer_key: &str,
     consumer_secret: &str,
-    token: &str,
+    token: Option<&str>,
     token_secret: &str,
+    extra_params: &[(&str, &str)],
     method: &str,
     url: &str,
 ) -> String {
@@ -78,24 +105,58 @@ fn build_oauth_header(
 
     let nonce = generate_nonce();
 
-    let mut params = BTreeMap::new();
-    params.insert("oauth_consumer_key", consumer_key.to_string());
-    params.insert("oauth_nonce", nonce.clone());
-    params.insert("oauth_signature_method", "HMAC-SHA1".to_string());
-    params.insert("oauth_timestamp", timestamp.clone());
-    params.insert("oauth_token", token.to_string());
-    params.insert("oauth_version", "1.0".to_string());
+    let mut oauth_params = BTreeMap::new();
+    oauth_params.insert("oauth_consumer_key", consumer_key.to_string());
+    oauth_params.insert("oauth_nonce", nonce);
+    oauth_params.insert("oauth_signature_method", "HMAC-SHA1".to_string());
+    oauth_params.insert("oauth_timestamp", timestamp);
+    if let Some(token) = token {
+        oauth_params.insert("oauth_token", token.to_string());
+    }
+    oauth_params.insert("oauth_version", "1.0".to_string());
+
+    let mut params: Vec<(&str, String)> =
+        oauth_params.iter().map(|(k, v)| (*k, v.clone())).collect();
+    for (k, v) in extra_params {
+        if k.starts_with("oauth_") {
+            oauth_params.insert(k, v.to_string());
+        }
+        params.push((k, v.to_string()));
+    }
 
     // Parse query params from URL and include them in the signature base
     let (base_url, query_params) = split_url(url);
-    for (k, v) in &query_params {
-        params.insert(k, v.clone());
-    }
+    params.extend(query_params);
+
+    let signature = sign(method, &base_url, &params, consumer_secret, token_secret);
+    oauth_params.insert("oauth_signature", signature);
+
+    // Build the Authorization header from the oauth_* params (sorted by key)
+    let header_params: String = oauth_params
+        .iter()
+        .map(|(k, v)| format!("{k}=\"{}\"", percent_encode(v)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("OAuth {header_params}")
+}
 
-    // Build the parameter string (sorted by key)
-    let param_string: String = params
+/// Compute the base64 HMAC-SHA1 signature for a request (RFC 5849 §3.4).
+fn sign(
+    method: &str,
+    base_url: &str,
+    params: &[(&str, String)],
+    consumer_secret: &str,
+    token_secret: &str,
+) -> String {
+    // Build the parameter string (sorted by encoded key, then encoded value)
+    let mut encoded: Vec<(String, String)> = params
+        .iter()
+        .map(|(k, v)| (percent_encode(k), percent_encode(v)))
+        .collect();
+    encoded.sort();
+    let param_string: String = encoded
         .iter()
-        .map(|(k, v)| format!("{}={}", percent_encode(k), percent_encode(v)))
+        .map(|(k, v)| format!("{k}={v}"))
         .collect::<Vec<_>>()
         .join("&");
 
@@ -103,7 +164,7 @@ fn build_oauth_header(
     let base_string = format!(
         "{}&{}&{}",
         method.to_uppercase(),
-        percent_encode(&base_url),
+        percent_encode(base_url),
         percent_encode(&param_string),
     );
 
@@ -116,17 +177,7 @@ fn build_oauth_header(
     let mut mac = Hmac::<Sha1>::new_from_slice(signing_key.as_bytes())
         .expect("HMAC can take key of any size");
     mac.update(base_string.as_bytes());
-    let signature = base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes());
-
-    // Build the Authorization header
-    format!(
-        "OAuth oauth_consumer_key=\"{}\", oauth_nonce=\"{}\", oauth_signature=\"{}\", oauth_signature_method=\"HMAC-SHA1\", oauth_timestamp=\"{}\", oauth_token=\"{}\", oauth_version=\"1.0\"",
-        percent_encode(consumer_key),
-        percent_encode(&nonce),
-        percent_encode(&signature),
-        percent_encode(&timestamp),
-        percent_encode(token),
-    )
+    base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
 }
 
 fn generate_nonce() -> String {
@@ -135,7 +186,7 @@ fn generate_nonce() -> String {
     hex_encode(&bytes)
 }
 
-fn hex_encode(bytes: &[u8]) -> String {
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{b:02x}")).collect()
 }
 
@@ -192,8 +243,9 @@ mod tests {
         let header = build_oauth_header(
             "consumer_key",
             "consumer_secret",
-            "token",
+            Some("token"),
             "token_secret",
+            &[],
             "GET",
             "https://api.discogs.com/artists/1",
         );
@@ -206,4 +258,52 @@ mod tests {
         assert!(header.contains("oauth_nonce="));
         assert!(header.contains("oauth_timestamp="));
     }
+
+    #[test]
+    fn test_oauth_signature_without_token() {
+        let header = build_oauth_header(
+            "consumer_key",
+            "consumer_secret",
+            None,
+            "",
+            &[("oauth_callback", "http://localhost/cb")],
+            "GET",
+            "https://api.discogs.com/oauth/request_token",
+        );
+        assert!(!header.contains("oauth_token="));
+        assert!(header.contains("oauth_callback=\"http%3A%2F%2Flocalhost%2Fcb\""));
+    }
+
+    #[test]
+    fn test_sign_known_vector() {
+        // Example request from Twitter's "Creating a signature" guide, which
+        // signs a form-encoded POST body alongside a query parameter.
+        let params = vec![
+            (
+                "status",
+                "Hello Ladies + Gentlemen, a signed OAuth request!".to_string(),
+            ),
+            ("include_entities", "true".to_string()),
+            ("oauth_consumer_key", "xvz1evFS4wEEPTGEFPHBog".to_string()),
+            (
+                "oauth_nonce",
+                "kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg".to_string(),
+            ),
+            ("oauth_signature_method", "HMAC-SHA1".to_string()),
+            ("oauth_timestamp", "1318622958".to_string()),
+            (
+                "oauth_token",
+                "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb".to_string(),
+            ),
+            ("oauth_version", "1.0".to_string()),
+        ];
+        let signature = sign(
+            "post",
+            "https://api.twitter.com/1.1/statuses/update.json",
+            &params,
+            "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw",
+            "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE",
+        );
+        assert_eq!(signature, "hCtSmYh+iHYCEqBWrE7C7hYmtUk=");
+    }
 }
@@ -18,17 +18,39 @@
 //! println!("{}", artist.name);
 //! ```
 
+use std::collections::BTreeMap;
+
 use bytes::Bytes;
+use futures_util::StreamExt;
 use tokio::runtime::Builder;
 
+use crate::cache::{Cache, CachePolicy};
 use crate::client::CoverArt;
 use crate::error::Result;
 use crate::models::artist::{Artist, ArtistRelease};
+use crate::models::collection::{
+    CollectionField, CollectionFolder, CollectionInstance, CollectionItem, CollectionItemsParams,
+    CollectionValue, InstanceEdit,
+};
+use crate::models::inventory::{
+    InventoryExport, InventoryExportRow, InventoryUpload, ListingChange, PollOptions, UploadListing,
+};
 use crate::models::label::{Label, LabelRelease};
+use crate::models::list::{List, ListSummary};
+use crate::models::listing::{Condition, CreatedListing, InventoryParams, Listing, ListingParams};
+use crate::models::marketplace::{
+    MarketplaceStats, Order, OrderMessage, OrderParams, OrderStatus, OrderUpdate,
+};
 use crate::models::master::{MasterRelease, MasterVersion};
-use crate::models::release::Release;
+use crate::models::money::{Currency, Money};
+use crate::models::release::{Release, ReleaseRating, ReleaseStats, UserReleaseRating};
 use crate::models::search::{SearchParams, SearchResult};
+use crate::models::user::{ContributionsParams, Identity, ProfileEdit, Submission, UserProfile};
+use crate::models::wantlist::{Want, WantEdit};
+use crate::oauth::{OAuthCredentials, RequestToken};
 use crate::pagination::{Paginated, PaginationParams};
+use crate::retry::RetryPolicy;
+use crate::stream::{PageStream, StreamOptions};
 use crate::DiscogsError;
 
 /// Blocking (synchronous) Discogs client.
@@ -72,20 +94,48 @@ impl ClientBuilder {
         token: impl Into<String>,
         token_secret: impl Into<String>,
     ) -> Self {
-        Self(self.0.oauth(consumer_key, consumer_secret, token, token_secret))
+        Self(
+            self.0
+                .oauth(consumer_key, consumer_secret, token, token_secret),
+        )
+    }
+
+    /// Authenticate with credentials obtained from an [`OAuthFlow`].
+    pub fn oauth_credentials(self, credentials: OAuthCredentials) -> Self {
+        Self(self.0.oauth_credentials(credentials))
+    }
+
+    /// Retry rate-limited and failed requests according to `policy`.
+    pub fn retry_policy(self, policy: RetryPolicy) -> Self {
+        Self(self.0.retry_policy(policy))
+    }
+
+    /// Cache GET responses in `cache`.
+    pub fn cache(self, cache: impl Cache + 'static) -> Self {
+        Self(self.0.cache(cache))
+    }
+
+    /// Set how long cached responses stay fresh.
+    pub fn cache_policy(self, policy: CachePolicy) -> Self {
+        Self(self.0.cache_policy(policy))
     }
 
     /// Build the blocking client.
     pub fn build(self) -> Result<DiscogsClient> {
         let inner = self.0.build()?;
-        let runtime = Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .map_err(|e| DiscogsError::Configuration(e.to_string()))?;
+        let runtime = new_runtime()?;
         Ok(DiscogsClient { inner, runtime })
     }
 }
 
+/// Build a single-threaded runtime for driving the async client.
+fn new_runtime() -> Result<tokio::runtime::Runtime> {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| DiscogsError::Configuration(e.to_string()))
+}
+
 impl DiscogsClient {
     /// Get an artist by ID.
     pub fn artist(&self, id: u64) -> Result<Artist> {
@@ -98,7 +148,8 @@ impl DiscogsClient {
         id: u64,
         pagination: &PaginationParams,
     ) -> Result<Paginated<ArtistRelease>> {
-        self.runtime.block_on(self.inner.artist_releases(id, pagination))
+        self.runtime
+            .block_on(self.inner.artist_releases(id, pagination))
     }
 
     /// Get a release by ID.
@@ -106,6 +157,57 @@ impl DiscogsClient {
         self.runtime.block_on(self.inner.release(id))
     }
 
+    /// Get a release with marketplace prices in the given currency.
+    pub fn release_with_currency(&self, id: u64, currency: Currency) -> Result<Release> {
+        self.runtime
+            .block_on(self.inner.release_with_currency(id, currency))
+    }
+
+    /// Get how many users have and want a release.
+    pub fn release_stats(&self, release_id: u64) -> Result<ReleaseStats> {
+        self.runtime.block_on(self.inner.release_stats(release_id))
+    }
+
+    /// Get have/want stats for many releases, keyed by release ID.
+    pub fn release_stats_batch(&self, release_ids: &[u64]) -> BTreeMap<u64, Result<ReleaseStats>> {
+        self.runtime
+            .block_on(self.inner.release_stats_batch(release_ids))
+    }
+
+    /// Get the community rating of a release.
+    pub fn release_rating(&self, release_id: u64) -> Result<ReleaseRating> {
+        self.runtime.block_on(self.inner.release_rating(release_id))
+    }
+
+    /// Get a user's rating of a release.
+    pub fn user_release_rating(
+        &self,
+        release_id: u64,
+        username: &str,
+    ) -> Result<UserReleaseRating> {
+        self.runtime
+            .block_on(self.inner.user_release_rating(release_id, username))
+    }
+
+    /// Set the authenticated user's rating of a release, from 1 to 5.
+    pub fn put_user_release_rating(
+        &self,
+        release_id: u64,
+        username: &str,
+        rating: u8,
+    ) -> Result<UserReleaseRating> {
+        self.runtime.block_on(
+            self.inner
+                .put_user_release_rating(release_id, username, rating),
+        )
+    }
+
+    /// Remove the authenticated user's rating of a release.
+    pub fn delete_user_release_rating(&self, release_id: u64, username: &str) -> Result<()> {
+        self.runtime
+            .block_on(self.inner.delete_user_release_rating(release_id, username))
+    }
+
     /// Get a label by ID.
     pub fn label(&self, id: u64) -> Result<Label> {
         self.runtime.block_on(self.inner.label(id))
@@ -117,7 +219,8 @@ impl DiscogsClient {
         id: u64,
         pagination: &PaginationParams,
     ) -> Result<Paginated<LabelRelease>> {
-        self.runtime.block_on(self.inner.label_releases(id, pagination))
+        self.runtime
+            .block_on(self.inner.label_releases(id, pagination))
     }
 
     /// Get a master release by ID.
@@ -131,7 +234,8 @@ impl DiscogsClient {
         id: u64,
         pagination: &PaginationParams,
     ) -> Result<Paginated<MasterVersion>> {
-        self.runtime.block_on(self.inner.master_versions(id, pagination))
+        self.runtime
+            .block_on(self.inner.master_versions(id, pagination))
     }
 
     /// Search the Discogs database.
@@ -143,6 +247,394 @@ impl DiscogsClient {
         self.runtime.block_on(self.inner.search(params, pagination))
     }
 
+    /// Get the identity of the authenticated user.
+    pub fn identity(&self) -> Result<Identity> {
+        self.runtime.block_on(self.inner.identity())
+    }
+
+    /// Get a user's profile.
+    pub fn user_profile(&self, username: &str) -> Result<UserProfile> {
+        self.runtime.block_on(self.inner.user_profile(username))
+    }
+
+    /// Edit the authenticated user's profile.
+    pub fn edit_user_profile(&self, username: &str, edit: &ProfileEdit) -> Result<UserProfile> {
+        self.runtime
+            .block_on(self.inner.edit_user_profile(username, edit))
+    }
+
+    /// Get the releases a user has contributed to.
+    pub fn contributions(
+        &self,
+        username: &str,
+        params: &ContributionsParams,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<Release>> {
+        self.runtime
+            .block_on(self.inner.contributions(username, params, pagination))
+    }
+
+    /// Get the artists, labels and releases a user has submitted edits to.
+    pub fn submissions(
+        &self,
+        username: &str,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<Submission>> {
+        self.runtime
+            .block_on(self.inner.submissions(username, pagination))
+    }
+
+    /// List the folders in a user's collection.
+    pub fn collection_folders(&self, username: &str) -> Result<Vec<CollectionFolder>> {
+        self.runtime
+            .block_on(self.inner.collection_folders(username))
+    }
+
+    /// Get a single collection folder.
+    pub fn collection_folder(&self, username: &str, folder_id: u64) -> Result<CollectionFolder> {
+        self.runtime
+            .block_on(self.inner.collection_folder(username, folder_id))
+    }
+
+    /// Create a new collection folder.
+    pub fn create_collection_folder(&self, username: &str, name: &str) -> Result<CollectionFolder> {
+        self.runtime
+            .block_on(self.inner.create_collection_folder(username, name))
+    }
+
+    /// Rename a collection folder.
+    pub fn rename_collection_folder(
+        &self,
+        username: &str,
+        folder_id: u64,
+        name: &str,
+    ) -> Result<CollectionFolder> {
+        self.runtime.block_on(
+            self.inner
+                .rename_collection_folder(username, folder_id, name),
+        )
+    }
+
+    /// Delete a collection folder. The folder must be empty.
+    pub fn delete_collection_folder(&self, username: &str, folder_id: u64) -> Result<()> {
+        self.runtime
+            .block_on(self.inner.delete_collection_folder(username, folder_id))
+    }
+
+    /// Get the items in a collection folder. Folder `0` lists the whole collection.
+    pub fn collection_items(
+        &self,
+        username: &str,
+        folder_id: u64,
+        params: &CollectionItemsParams,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<CollectionItem>> {
+        self.runtime.block_on(
+            self.inner
+                .collection_items(username, folder_id, params, pagination),
+        )
+    }
+
+    /// List the instances of a release in a user's collection, across all folders.
+    pub fn collection_items_by_release(
+        &self,
+        username: &str,
+        release_id: u64,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<CollectionItem>> {
+        self.runtime.block_on(
+            self.inner
+                .collection_items_by_release(username, release_id, pagination),
+        )
+    }
+
+    /// Add a release to a collection folder, returning the new instance.
+    pub fn add_to_collection(
+        &self,
+        username: &str,
+        folder_id: u64,
+        release_id: u64,
+    ) -> Result<CollectionInstance> {
+        self.runtime.block_on(
+            self.inner
+                .add_to_collection(username, folder_id, release_id),
+        )
+    }
+
+    /// Change the rating or folder of a collection instance.
+    pub fn edit_collection_instance(
+        &self,
+        username: &str,
+        folder_id: u64,
+        release_id: u64,
+        instance_id: u64,
+        edit: &InstanceEdit,
+    ) -> Result<()> {
+        self.runtime.block_on(self.inner.edit_collection_instance(
+            username,
+            folder_id,
+            release_id,
+            instance_id,
+            edit,
+        ))
+    }
+
+    /// Remove an instance of a release from a collection folder.
+    pub fn delete_collection_instance(
+        &self,
+        username: &str,
+        folder_id: u64,
+        release_id: u64,
+        instance_id: u64,
+    ) -> Result<()> {
+        self.runtime.block_on(self.inner.delete_collection_instance(
+            username,
+            folder_id,
+            release_id,
+            instance_id,
+        ))
+    }
+
+    /// List the custom notes fields defined for a user's collection.
+    pub fn collection_fields(&self, username: &str) -> Result<Vec<CollectionField>> {
+        self.runtime
+            .block_on(self.inner.collection_fields(username))
+    }
+
+    /// Set the value of a custom notes field on a collection instance.
+    pub fn edit_instance_field(
+        &self,
+        username: &str,
+        folder_id: u64,
+        release_id: u64,
+        instance_id: u64,
+        field_id: u64,
+        value: &str,
+    ) -> Result<()> {
+        self.runtime.block_on(self.inner.edit_instance_field(
+            username,
+            folder_id,
+            release_id,
+            instance_id,
+            field_id,
+            value,
+        ))
+    }
+
+    /// Get the estimated minimum, median and maximum value of a user's collection.
+    pub fn collection_value(&self, username: &str) -> Result<CollectionValue> {
+        self.runtime.block_on(self.inner.collection_value(username))
+    }
+
+    /// Get a user's wantlist.
+    pub fn wantlist(
+        &self,
+        username: &str,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<Want>> {
+        self.runtime
+            .block_on(self.inner.wantlist(username, pagination))
+    }
+
+    /// Add a release to a user's wantlist.
+    pub fn add_want(&self, username: &str, release_id: u64, edit: &WantEdit) -> Result<Want> {
+        self.runtime
+            .block_on(self.inner.add_want(username, release_id, edit))
+    }
+
+    /// Change the notes or rating of a wantlist entry.
+    pub fn edit_want(&self, username: &str, release_id: u64, edit: &WantEdit) -> Result<Want> {
+        self.runtime
+            .block_on(self.inner.edit_want(username, release_id, edit))
+    }
+
+    /// Remove a release from a user's wantlist.
+    pub fn delete_want(&self, username: &str, release_id: u64) -> Result<()> {
+        self.runtime
+            .block_on(self.inner.delete_want(username, release_id))
+    }
+
+    /// Get a user's public lists, or all of them for the authenticated user.
+    pub fn user_lists(
+        &self,
+        username: &str,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<ListSummary>> {
+        self.runtime
+            .block_on(self.inner.user_lists(username, pagination))
+    }
+
+    /// Get a list and its items.
+    pub fn list(&self, list_id: u64) -> Result<List> {
+        self.runtime.block_on(self.inner.list(list_id))
+    }
+
+    /// Browse a seller's inventory.
+    pub fn user_inventory(
+        &self,
+        username: &str,
+        params: &InventoryParams,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<Listing>> {
+        self.runtime
+            .block_on(self.inner.user_inventory(username, params, pagination))
+    }
+
+    /// Get a marketplace listing by ID.
+    pub fn listing(&self, listing_id: u64) -> Result<Listing> {
+        self.runtime.block_on(self.inner.listing(listing_id))
+    }
+
+    /// Create a marketplace listing.
+    pub fn create_listing(&self, params: &ListingParams) -> Result<CreatedListing> {
+        self.runtime.block_on(self.inner.create_listing(params))
+    }
+
+    /// Replace the details of an existing marketplace listing.
+    pub fn edit_listing(&self, listing_id: u64, params: &ListingParams) -> Result<()> {
+        self.runtime
+            .block_on(self.inner.edit_listing(listing_id, params))
+    }
+
+    /// Delete a marketplace listing.
+    pub fn delete_listing(&self, listing_id: u64) -> Result<()> {
+        self.runtime.block_on(self.inner.delete_listing(listing_id))
+    }
+
+    /// Get marketplace statistics for a release.
+    pub fn marketplace_stats(&self, release_id: u64) -> Result<MarketplaceStats> {
+        self.runtime
+            .block_on(self.inner.marketplace_stats(release_id))
+    }
+
+    /// Calculate the fee Discogs would charge on a sale at `price`.
+    pub fn marketplace_fee(&self, price: f64, currency: Currency) -> Result<Money> {
+        self.runtime
+            .block_on(self.inner.marketplace_fee(price, currency))
+    }
+
+    /// Get suggested prices for a release in each media condition.
+    pub fn price_suggestions(&self, release_id: u64) -> Result<BTreeMap<Condition, Money>> {
+        self.runtime
+            .block_on(self.inner.price_suggestions(release_id))
+    }
+
+    /// List the authenticated seller's orders.
+    pub fn orders(
+        &self,
+        params: &OrderParams,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<Order>> {
+        self.runtime.block_on(self.inner.orders(params, pagination))
+    }
+
+    /// Get a marketplace order by ID.
+    pub fn order(&self, order_id: &str) -> Result<Order> {
+        self.runtime.block_on(self.inner.order(order_id))
+    }
+
+    /// Change an order's status or shipping cost, returning the updated order.
+    pub fn edit_order(&self, order: &Order, update: &OrderUpdate) -> Result<Order> {
+        self.runtime.block_on(self.inner.edit_order(order, update))
+    }
+
+    /// Get the message thread of an order, newest first.
+    pub fn order_messages(
+        &self,
+        order_id: &str,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<OrderMessage>> {
+        self.runtime
+            .block_on(self.inner.order_messages(order_id, pagination))
+    }
+
+    /// Add a message to an order's thread, optionally changing its status.
+    pub fn post_order_message(
+        &self,
+        order_id: &str,
+        message: Option<&str>,
+        status: Option<OrderStatus>,
+    ) -> Result<OrderMessage> {
+        self.runtime
+            .block_on(self.inner.post_order_message(order_id, message, status))
+    }
+
+    /// Start exporting the authenticated seller's inventory to CSV,
+    /// returning the export ID.
+    pub fn request_inventory_export(&self) -> Result<u64> {
+        self.runtime.block_on(self.inner.request_inventory_export())
+    }
+
+    /// List the authenticated seller's recent inventory exports.
+    pub fn inventory_exports(
+        &self,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<InventoryExport>> {
+        self.runtime
+            .block_on(self.inner.inventory_exports(pagination))
+    }
+
+    /// Get the status of an inventory export.
+    pub fn inventory_export(&self, export_id: u64) -> Result<InventoryExport> {
+        self.runtime
+            .block_on(self.inner.inventory_export(export_id))
+    }
+
+    /// Download the CSV of a finished inventory export.
+    pub fn download_inventory_export(&self, export_id: u64) -> Result<Bytes> {
+        self.runtime
+            .block_on(self.inner.download_inventory_export(export_id))
+    }
+
+    /// Export the authenticated seller's inventory: request an export, wait
+    /// for it to finish, then download and parse the CSV.
+    pub fn export_inventory(&self, options: &PollOptions) -> Result<Vec<InventoryExportRow>> {
+        self.runtime.block_on(self.inner.export_inventory(options))
+    }
+
+    /// Upload new listings in bulk, returning the upload ID.
+    pub fn upload_inventory_add(&self, listings: &[UploadListing]) -> Result<u64> {
+        self.runtime
+            .block_on(self.inner.upload_inventory_add(listings))
+    }
+
+    /// Change existing listings in bulk, returning the upload IDs.
+    pub fn upload_inventory_change(&self, changes: &[ListingChange]) -> Result<Vec<u64>> {
+        self.runtime
+            .block_on(self.inner.upload_inventory_change(changes))
+    }
+
+    /// Delete listings in bulk, returning the upload ID.
+    pub fn upload_inventory_delete(&self, listing_ids: &[u64]) -> Result<u64> {
+        self.runtime
+            .block_on(self.inner.upload_inventory_delete(listing_ids))
+    }
+
+    /// List the authenticated seller's recent inventory uploads.
+    pub fn inventory_uploads(
+        &self,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<InventoryUpload>> {
+        self.runtime
+            .block_on(self.inner.inventory_uploads(pagination))
+    }
+
+    /// Get the status of an inventory upload.
+    pub fn inventory_upload(&self, upload_id: u64) -> Result<InventoryUpload> {
+        self.runtime
+            .block_on(self.inner.inventory_upload(upload_id))
+    }
+
+    /// Wait for an inventory upload to finish, returning it with its results.
+    pub fn wait_for_inventory_upload(
+        &self,
+        upload_id: u64,
+        options: &PollOptions,
+    ) -> Result<InventoryUpload> {
+        self.runtime
+            .block_on(self.inner.wait_for_inventory_upload(upload_id, options))
+    }
+
     /// Download an image from a Discogs image URL, returning the raw bytes.
     pub fn download_image(&self, url: &str) -> Result<Bytes> {
         self.runtime.block_on(self.inner.download_image(url))
@@ -153,3 +645,257 @@ impl DiscogsClient {
         self.runtime.block_on(self.inner.release_cover_art(id))
     }
 }
+
+/// A blocking iterator over the items of a paginated endpoint, fetching
+/// pages as they are needed.
+///
+/// Returned by the `*_iter` methods of [`DiscogsClient`]. Iteration ends
+/// after the last page or the first error. The client's runtime only runs
+/// while a call is in progress, so a prefetched page is fetched during
+/// calls to `next` rather than between them.
+pub struct PageIter<'a, T> {
+    stream: PageStream<T>,
+    runtime: &'a tokio::runtime::Runtime,
+}
+
+impl<T: Send + 'static> Iterator for PageIter<'_, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl DiscogsClient {
+    fn iter<T>(&self, stream: PageStream<T>) -> PageIter<'_, T> {
+        PageIter {
+            stream,
+            runtime: &self.runtime,
+        }
+    }
+
+    /// Iterate over all of an artist's releases.
+    pub fn artist_releases_iter(
+        &self,
+        id: u64,
+        options: &StreamOptions,
+    ) -> PageIter<'_, ArtistRelease> {
+        self.iter(self.inner.artist_releases_stream(id, options))
+    }
+
+    /// Iterate over all of a label's releases.
+    pub fn label_releases_iter(
+        &self,
+        id: u64,
+        options: &StreamOptions,
+    ) -> PageIter<'_, LabelRelease> {
+        self.iter(self.inner.label_releases_stream(id, options))
+    }
+
+    /// Iterate over all versions of a master release.
+    pub fn master_versions_iter(
+        &self,
+        id: u64,
+        options: &StreamOptions,
+    ) -> PageIter<'_, MasterVersion> {
+        self.iter(self.inner.master_versions_stream(id, options))
+    }
+
+    /// Iterate over all results of a database search.
+    pub fn search_iter(
+        &self,
+        params: &SearchParams,
+        options: &StreamOptions,
+    ) -> PageIter<'_, SearchResult> {
+        self.iter(self.inner.search_stream(params, options))
+    }
+
+    /// Iterate over all of a user's contributions.
+    pub fn contributions_iter(
+        &self,
+        username: &str,
+        params: &ContributionsParams,
+        options: &StreamOptions,
+    ) -> PageIter<'_, Release> {
+        self.iter(self.inner.contributions_stream(username, params, options))
+    }
+
+    /// Iterate over all of a user's submissions.
+    pub fn submissions_iter(
+        &self,
+        username: &str,
+        options: &StreamOptions,
+    ) -> PageIter<'_, Submission> {
+        self.iter(self.inner.submissions_stream(username, options))
+    }
+
+    /// Iterate over all items in a collection folder.
+    pub fn collection_items_iter(
+        &self,
+        username: &str,
+        folder_id: u64,
+        params: &CollectionItemsParams,
+        options: &StreamOptions,
+    ) -> PageIter<'_, CollectionItem> {
+        let stream = self
+            .inner
+            .collection_items_stream(username, folder_id, params, options);
+        self.iter(stream)
+    }
+
+    /// Iterate over all instances of a release in a user's collection.
+    pub fn collection_items_by_release_iter(
+        &self,
+        username: &str,
+        release_id: u64,
+        options: &StreamOptions,
+    ) -> PageIter<'_, CollectionItem> {
+        let stream = self
+            .inner
+            .collection_items_by_release_stream(username, release_id, options);
+        self.iter(stream)
+    }
+
+    /// Iterate over a user's whole wantlist.
+    pub fn wantlist_iter(&self, username: &str, options: &StreamOptions) -> PageIter<'_, Want> {
+        self.iter(self.inner.wantlist_stream(username, options))
+    }
+
+    /// Iterate over all of a user's lists.
+    pub fn user_lists_iter(
+        &self,
+        username: &str,
+        options: &StreamOptions,
+    ) -> PageIter<'_, ListSummary> {
+        self.iter(self.inner.user_lists_stream(username, options))
+    }
+
+    /// Iterate over a seller's whole inventory.
+    pub fn user_inventory_iter(
+        &self,
+        username: &str,
+        params: &InventoryParams,
+        options: &StreamOptions,
+    ) -> PageIter<'_, Listing> {
+        self.iter(self.inner.user_inventory_stream(username, params, options))
+    }
+
+    /// Iterate over all marketplace orders.
+    pub fn orders_iter(
+        &self,
+        params: &OrderParams,
+        options: &StreamOptions,
+    ) -> PageIter<'_, Order> {
+        self.iter(self.inner.orders_stream(params, options))
+    }
+
+    /// Iterate over all messages on an order.
+    pub fn order_messages_iter(
+        &self,
+        order_id: &str,
+        options: &StreamOptions,
+    ) -> PageIter<'_, OrderMessage> {
+        self.iter(self.inner.order_messages_stream(order_id, options))
+    }
+
+    /// Iterate over all inventory exports.
+    pub fn inventory_exports_iter(&self, options: &StreamOptions) -> PageIter<'_, InventoryExport> {
+        self.iter(self.inner.inventory_exports_stream(options))
+    }
+
+    /// Iterate over all inventory uploads.
+    pub fn inventory_uploads_iter(&self, options: &StreamOptions) -> PageIter<'_, InventoryUpload> {
+        self.iter(self.inner.inventory_uploads_stream(options))
+    }
+}
+
+/// Blocking OAuth 1.0a three-legged authorization flow.
+///
+/// See [`crate::OAuthFlow`] for the sequence of calls.
+pub struct OAuthFlow {
+    inner: crate::OAuthFlow,
+    runtime: tokio::runtime::Runtime,
+}
+
+/// Builder for a blocking [`OAuthFlow`].
+pub struct OAuthFlowBuilder(crate::OAuthFlowBuilder);
+
+impl OAuthFlow {
+    /// Create a new blocking flow builder.
+    pub fn builder() -> OAuthFlowBuilder {
+        OAuthFlowBuilder(crate::OAuthFlow::builder())
+    }
+
+    /// Fetch a request token for the given callback URL (or `"oob"`).
+    pub fn request_token(&self, callback_url: &str) -> Result<RequestToken> {
+        self.runtime
+            .block_on(self.inner.request_token(callback_url))
+    }
+
+    /// The URL the user must visit to approve the request token.
+    pub fn authorize_url(&self, request_token: &RequestToken) -> String {
+        self.inner.authorize_url(request_token)
+    }
+
+    /// Exchange an approved request token and its verifier for access credentials.
+    pub fn access_token(
+        &self,
+        request_token: &RequestToken,
+        verifier: &str,
+    ) -> Result<OAuthCredentials> {
+        self.runtime
+            .block_on(self.inner.access_token(request_token, verifier))
+    }
+
+    /// Run the whole authorization flow, capturing the verifier on a local
+    /// loopback listener.
+    #[cfg(feature = "oauth-loopback")]
+    pub fn authorize_with_loopback<F>(
+        &self,
+        options: &crate::LoopbackOptions,
+        open_authorize_url: F,
+    ) -> Result<OAuthCredentials>
+    where
+        F: FnOnce(&str),
+    {
+        self.runtime.block_on(
+            self.inner
+                .authorize_with_loopback(options, open_authorize_url),
+        )
+    }
+}
+
+impl OAuthFlowBuilder {
+    /// Set the User-Agent header (required by Discogs API).
+    pub fn user_agent(self, ua: impl Into<String>) -> Self {
+        Self(self.0.user_agent(ua))
+    }
+
+    /// Override the base URL. For testing only.
+    #[doc(hidden)]
+    pub fn base_url(self, url: impl Into<String>) -> Self {
+        Self(self.0.base_url(url))
+    }
+
+    /// Override the authorization page URL. For testing only.
+    #[doc(hidden)]
+    pub fn authorize_url(self, url: impl Into<String>) -> Self {
+        Self(self.0.authorize_url(url))
+    }
+
+    /// Set the application's consumer key and secret.
+    pub fn consumer(
+        self,
+        consumer_key: impl Into<String>,
+        consumer_secret: impl Into<String>,
+    ) -> Self {
+        Self(self.0.consumer(consumer_key, consumer_secret))
+    }
+
+    /// Build the blocking flow.
+    pub fn build(self) -> Result<OAuthFlow> {
+        let inner = self.0.build()?;
+        let runtime = new_runtime()?;
+        Ok(OAuthFlow { inner, runtime })
+    }
+}
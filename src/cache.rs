@@ -0,0 +1,473 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+
+use crate::auth::hex_encode;
+
+/// Storage for cached responses, consulted by the client before sending a
+/// GET request.
+///
+/// Methods are called inline on the request path, so implementations should
+/// be quick. A cache that fails to read or write should behave as if the
+/// entry were missing rather than fail the request.
+///
+/// [`FileCache`] and `SqliteCache` do blocking disk I/O in these methods,
+/// holding up the runtime worker thread they are called from. That is fine
+/// for most clients; an application making many concurrent requests on a
+/// small runtime may prefer a cache that hands work to a dedicated thread.
+pub trait Cache: Send + Sync {
+    /// Look up the entry stored under `key`.
+    fn get(&self, key: &str) -> Option<CacheEntry>;
+
+    /// Store `entry` under `key`, replacing any existing entry.
+    fn put(&self, key: &str, entry: CacheEntry);
+
+    /// Remove the entry stored under `key`, if any.
+    fn remove(&self, key: &str);
+
+    /// Remove every entry whose key `matches`. Called after a write, with
+    /// the responses it may have changed.
+    fn remove_matching(&self, matches: &dyn Fn(&str) -> bool);
+}
+
+impl<C: Cache + ?Sized> Cache for Arc<C> {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry) {
+        (**self).put(key, entry)
+    }
+
+    fn remove(&self, key: &str) {
+        (**self).remove(key)
+    }
+
+    fn remove_matching(&self, matches: &dyn Fn(&str) -> bool) {
+        (**self).remove_matching(matches)
+    }
+}
+
+/// A cached response body and the validators needed to revalidate it.
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub body: Bytes,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// When the entry stops being fresh and has to be revalidated.
+    pub expires_at: SystemTime,
+}
+
+impl CacheEntry {
+    /// Whether the entry can be used without asking the server.
+    pub fn is_fresh(&self) -> bool {
+        SystemTime::now() < self.expires_at
+    }
+
+    /// Whether the server can confirm the entry is unchanged, making it worth
+    /// keeping after it goes stale.
+    pub fn has_validators(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+/// When an entry stored now with `ttl` stops being fresh. TTLs reaching
+/// past the year 9999, such as `Duration::MAX`, are capped there so they
+/// neither overflow `SystemTime` nor the timestamps caches store.
+pub(crate) fn expiry_after(ttl: Duration) -> SystemTime {
+    let latest = UNIX_EPOCH + Duration::from_secs(253_402_300_799);
+    SystemTime::now()
+        .checked_add(ttl)
+        .map_or(latest, |expires_at| expires_at.min(latest))
+}
+
+/// How long responses stay fresh, by path prefix.
+///
+/// The longest prefix matching a request path decides its TTL; paths with no
+/// match use `default_ttl`. A zero TTL means entries are always revalidated,
+/// and are only kept if the server sent an `ETag` or `Last-Modified`.
+/// A TTL of `Duration::MAX` keeps entries fresh indefinitely.
+///
+/// By default artists, labels, masters and releases are fresh for an hour,
+/// while user, collection and marketplace data is always revalidated.
+/// Images from [`download_image`](crate::DiscogsClient::download_image) are
+/// fresh for 30 days.
+///
+/// A successful write through the client removes the cached responses to
+/// its path and the paths above and below it, with any query parameters:
+/// rating a release removes `/releases/{id}/rating` and `/releases/{id}`.
+/// Other listings the write changes, such as search results, stay cached
+/// until they expire.
+#[derive(Debug, Clone)]
+pub struct CachePolicy {
+    pub default_ttl: Duration,
+    pub ttls: Vec<(String, Duration)>,
+    pub image_ttl: Duration,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        let hour = Duration::from_secs(60 * 60);
+        Self {
+            default_ttl: Duration::ZERO,
+            ttls: ["/artists", "/labels", "/masters", "/releases"]
+                .iter()
+                .map(|prefix| (prefix.to_string(), hour))
+                .collect(),
+            image_ttl: 30 * 24 * hour,
+        }
+    }
+}
+
+impl CachePolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// Set the TTL of responses whose path starts with `prefix`, e.g.
+    /// `"/releases"`.
+    pub fn ttl(mut self, prefix: impl Into<String>, ttl: Duration) -> Self {
+        let prefix = prefix.into();
+        self.ttls.retain(|(p, _)| *p != prefix);
+        self.ttls.push((prefix, ttl));
+        self
+    }
+
+    pub fn image_ttl(mut self, ttl: Duration) -> Self {
+        self.image_ttl = ttl;
+        self
+    }
+
+    /// The TTL for responses to `path`.
+    pub(crate) fn ttl_for(&self, path: &str) -> Duration {
+        self.ttls
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default_ttl, |(_, ttl)| *ttl)
+    }
+}
+
+/// An in-memory cache holding up to a fixed number of entries, evicting the
+/// least recently used.
+pub struct MemoryCache {
+    capacity: usize,
+    inner: Mutex<MemoryCacheInner>,
+}
+
+#[derive(Default)]
+struct MemoryCacheInner {
+    entries: HashMap<String, (CacheEntry, u64)>,
+    /// Keys by the tick they were last used at, oldest first.
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(MemoryCacheInner::default()),
+        }
+    }
+
+    /// The number of entries currently cached.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl MemoryCacheInner {
+    /// Mark `key` as just used and return its new tick.
+    fn touch(&mut self, key: &str, previous: Option<u64>) -> u64 {
+        if let Some(previous) = previous {
+            self.recency.remove(&previous);
+        }
+        self.tick += 1;
+        self.recency.insert(self.tick, key.to_string());
+        self.tick
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let mut inner = self.inner.lock().unwrap();
+        let previous = inner.entries.get(key)?.1;
+        let tick = inner.touch(key, Some(previous));
+        let (entry, used) = inner.entries.get_mut(key)?;
+        *used = tick;
+        Some(entry.clone())
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        let previous = inner.entries.get(key).map(|(_, used)| *used);
+        let tick = inner.touch(key, previous);
+        inner.entries.insert(key.to_string(), (entry, tick));
+        while inner.entries.len() > self.capacity {
+            let Some((_, oldest)) = inner.recency.pop_first() else {
+                break;
+            };
+            inner.entries.remove(&oldest);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some((_, used)) = inner.entries.remove(key) {
+            inner.recency.remove(&used);
+        }
+    }
+
+    fn remove_matching(&self, matches: &dyn Fn(&str) -> bool) {
+        let inner = &mut *self.inner.lock().unwrap();
+        inner.entries.retain(|key, (_, used)| {
+            let keep = !matches(key);
+            if !keep {
+                inner.recency.remove(used);
+            }
+            keep
+        });
+    }
+}
+
+/// A cache storing one file per entry in a directory, so that responses
+/// survive between runs.
+///
+/// Entries are never evicted; remove the directory to clear the cache.
+pub struct FileCache {
+    dir: PathBuf,
+}
+
+/// The first line of a [`FileCache`] file, followed by the body.
+#[derive(Serialize, Deserialize)]
+struct FileHeader {
+    key: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Seconds since the Unix epoch.
+    expires_at: u64,
+}
+
+impl FileCache {
+    /// Use `dir` for cache files, creating it if needed.
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(hex_encode(&Sha1::digest(key.as_bytes())))
+    }
+
+    fn read(&self, key: &str) -> io::Result<Option<CacheEntry>> {
+        let mut reader = match fs::File::open(self.path(key)) {
+            Ok(file) => BufReader::new(file),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let header: FileHeader = serde_json::from_str(&line)?;
+        // Another key with the same hash.
+        if header.key != key {
+            return Ok(None);
+        }
+        let mut body = Vec::new();
+        reader.read_to_end(&mut body)?;
+        Ok(Some(CacheEntry {
+            body: body.into(),
+            etag: header.etag,
+            last_modified: header.last_modified,
+            expires_at: UNIX_EPOCH + Duration::from_secs(header.expires_at),
+        }))
+    }
+
+    fn write(&self, key: &str, entry: &CacheEntry) -> io::Result<()> {
+        let header = FileHeader {
+            key: key.to_string(),
+            etag: entry.etag.clone(),
+            last_modified: entry.last_modified.clone(),
+            expires_at: entry
+                .expires_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+        // Write to a temporary file first so readers never see a partial entry.
+        // The name is unique so that concurrent writers, in this process or
+        // another sharing the directory, don't interleave.
+        static WRITES: AtomicU64 = AtomicU64::new(0);
+        let path = self.path(key);
+        let tmp = path.with_extension(format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+        let written = (|| {
+            let mut file = fs::File::create(&tmp)?;
+            serde_json::to_writer(&mut file, &header)?;
+            file.write_all(b"\n")?;
+            file.write_all(&entry.body)?;
+            fs::rename(&tmp, path)
+        })();
+        if written.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        written
+    }
+}
+
+impl Cache for FileCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        self.read(key).ok().flatten()
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry) {
+        let _ = self.write(key, &entry);
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+    }
+
+    /// Reads the key of every file in the directory, so takes longer the
+    /// more entries are cached.
+    fn remove_matching(&self, matches: &dyn Fn(&str) -> bool) {
+        let Ok(files) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for path in files.filter_map(|file| Some(file.ok()?.path())) {
+            if read_header(&path).is_ok_and(|header| matches(&header.key)) {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// Read the header line of a [`FileCache`] file.
+fn read_header(path: &Path) -> io::Result<FileHeader> {
+    let mut line = String::new();
+    BufReader::new(fs::File::open(path)?).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(body: &str) -> CacheEntry {
+        CacheEntry {
+            body: Bytes::copy_from_slice(body.as_bytes()),
+            etag: Some("\"v1\"".into()),
+            last_modified: None,
+            expires_at: UNIX_EPOCH + Duration::from_secs(2_000_000_000),
+        }
+    }
+
+    #[test]
+    fn test_memory_cache_evicts_least_recently_used() {
+        let cache = MemoryCache::new(2);
+        cache.put("a", entry("a"));
+        cache.put("b", entry("b"));
+        assert!(cache.get("a").is_some());
+        cache.put("c", entry("c"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("b").is_none());
+        assert_eq!(&cache.get("a").unwrap().body[..], b"a");
+        assert_eq!(&cache.get("c").unwrap().body[..], b"c");
+
+        cache.remove("a");
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.len(), 1);
+
+        cache.put("d", entry("d"));
+        cache.remove_matching(&|key| key != "d");
+        assert_eq!(cache.len(), 1);
+        cache.put("e", entry("e"));
+        assert!(cache.get("d").is_some());
+    }
+
+    #[test]
+    fn test_file_cache_round_trips_entries() {
+        let dir = std::env::temp_dir().join(format!("discogger-cache-{}", std::process::id()));
+        let cache = FileCache::new(&dir).unwrap();
+        assert!(cache.get("GET /artists/1").is_none());
+
+        cache.put("GET /artists/1", entry("{\"id\": 1}\n"));
+        let cached = cache.get("GET /artists/1").unwrap();
+        assert_eq!(&cached.body[..], b"{\"id\": 1}\n");
+        assert_eq!(cached.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(cached.expires_at, entry("").expires_at);
+
+        cache.remove("GET /artists/1");
+        assert!(cache.get("GET /artists/1").is_none());
+
+        cache.put("GET /artists/1", entry("1"));
+        cache.put("GET /artists/2", entry("2"));
+        cache.remove_matching(&|key| key.ends_with('1'));
+        assert!(cache.get("GET /artists/1").is_none());
+        assert!(cache.get("GET /artists/2").is_some());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_cache_concurrent_writes_leave_whole_entries() {
+        let dir = std::env::temp_dir().join(format!("discogger-race-{}", std::process::id()));
+        let cache = Arc::new(FileCache::new(&dir).unwrap());
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let cache = cache.clone();
+                std::thread::spawn(move || cache.write("key", &entry(&i.to_string().repeat(4096))))
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap().unwrap();
+        }
+
+        let body = cache.get("key").unwrap().body;
+        assert_eq!(body.len(), 4096);
+        assert!(body.iter().all(|b| *b == body[0]));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_expiry_after_caps_huge_ttls() {
+        let forever = expiry_after(Duration::MAX);
+        assert!(forever > SystemTime::now() + Duration::from_secs(1_000 * 365 * 24 * 60 * 60));
+        assert_eq!(expiry_after(Duration::from_secs(u64::MAX / 2)), forever);
+    }
+
+    #[test]
+    fn test_policy_uses_longest_matching_prefix() {
+        let policy = CachePolicy::new()
+            .default_ttl(Duration::from_secs(5))
+            .ttl("/releases/1/rating", Duration::ZERO);
+        assert_eq!(policy.ttl_for("/releases/1"), Duration::from_secs(3600));
+        assert_eq!(policy.ttl_for("/releases/1/rating/someone"), Duration::ZERO);
+        assert_eq!(policy.ttl_for("/users/someone"), Duration::from_secs(5));
+    }
+}
@@ -1,26 +1,63 @@
+use std::collections::BTreeMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bytes::Bytes;
-use reqwest::Client;
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
-use crate::auth::Auth;
+use crate::auth::{percent_encode, Auth};
+use crate::cache::{expiry_after, Cache, CacheEntry, CachePolicy};
 use crate::error::{DiscogsError, Result};
 use crate::models::artist::{Artist, ArtistRelease};
+use crate::models::collection::{
+    CollectionField, CollectionFolder, CollectionInstance, CollectionItem, CollectionItemsParams,
+    CollectionValue, InstanceEdit,
+};
+use crate::models::inventory::{
+    delete_csv, InventoryExport, InventoryExportRow, InventoryUpload, JobStatus, ListingChange,
+    PollOptions, UploadListing,
+};
 use crate::models::label::{Label, LabelRelease};
+use crate::models::list::{List, ListSummary};
+use crate::models::listing::{Condition, CreatedListing, InventoryParams, Listing, ListingParams};
+use crate::models::marketplace::{
+    MarketplaceStats, Order, OrderMessage, OrderParams, OrderStatus, OrderUpdate,
+};
 use crate::models::master::{MasterRelease, MasterVersion};
-use crate::models::release::Release;
+use crate::models::money::{Currency, Money};
+use crate::models::release::{Release, ReleaseRating, ReleaseStats, UserReleaseRating};
 use crate::models::search::{SearchParams, SearchResult};
-use crate::models::Image;
+use crate::models::user::{
+    ContributionsParams, Identity, ProfileEdit, Submission, SubmissionGroups, UserProfile,
+};
+use crate::models::wantlist::{Want, WantEdit};
+use crate::models::{Image, PaginationInfo};
+use crate::oauth::OAuthCredentials;
 use crate::pagination::{Paginated, PaginatedResponse, PaginationParams};
 use crate::rate_limit::RateLimiter;
+use crate::retry::{retry_after, RetryPolicy};
 
 const BASE_URL: &str = "https://api.discogs.com";
 
+/// Requests kept in flight by batch helpers. The rate limiter still decides
+/// when each one is actually sent.
+const BATCH_CONCURRENCY: usize = 4;
+
 struct Inner {
     http: Client,
     auth: Option<Auth>,
     rate_limiter: RateLimiter,
+    retry_policy: Option<RetryPolicy>,
+    cache: Option<Box<dyn Cache>>,
+    cache_policy: CachePolicy,
     base_url: String,
 }
 
@@ -36,6 +73,9 @@ pub struct DiscogsClient {
 pub struct ClientBuilder {
     user_agent: Option<String>,
     auth: Option<Auth>,
+    retry_policy: Option<RetryPolicy>,
+    cache: Option<Box<dyn Cache>>,
+    cache_policy: CachePolicy,
     base_url: String,
 }
 
@@ -44,6 +84,9 @@ impl ClientBuilder {
         Self {
             user_agent: None,
             auth: None,
+            retry_policy: None,
+            cache: None,
+            cache_policy: CachePolicy::default(),
             base_url: BASE_URL.to_string(),
         }
     }
@@ -84,6 +127,36 @@ impl ClientBuilder {
         self
     }
 
+    /// Authenticate with credentials obtained from an [`OAuthFlow`](crate::OAuthFlow).
+    pub fn oauth_credentials(self, credentials: OAuthCredentials) -> Self {
+        self.oauth(
+            credentials.consumer_key,
+            credentials.consumer_secret,
+            credentials.token,
+            credentials.token_secret,
+        )
+    }
+
+    /// Retry rate-limited and failed requests according to `policy`.
+    /// By default requests are not retried.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Cache GET responses in `cache`. Fresh entries are returned without a
+    /// request, so they don't count against the rate limit.
+    pub fn cache(mut self, cache: impl Cache + 'static) -> Self {
+        self.cache = Some(Box::new(cache));
+        self
+    }
+
+    /// Set how long cached responses stay fresh.
+    pub fn cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.cache_policy = policy;
+        self
+    }
+
     /// Build the client.
     pub fn build(self) -> Result<DiscogsClient> {
         let user_agent = self.user_agent.ok_or_else(|| {
@@ -108,6 +181,9 @@ impl ClientBuilder {
                 http,
                 auth: self.auth,
                 rate_limiter: RateLimiter::new(max_per_minute),
+                retry_policy: self.retry_policy,
+                cache: self.cache,
+                cache_policy: self.cache_policy,
                 base_url: self.base_url,
             }),
         })
@@ -120,70 +196,233 @@ impl DiscogsClient {
         ClientBuilder::new()
     }
 
-    /// Internal GET helper that handles auth, rate limiting, and error responses.
+    /// Internal GET helper that handles auth, rate limiting, caching, and
+    /// error responses.
     async fn get<T: DeserializeOwned>(&self, path: &str, query: &[(&str, String)]) -> Result<T> {
-        self.inner.rate_limiter.acquire().await;
+        let Some(cache) = &self.inner.cache else {
+            let response = self.send(Method::GET, path, query, Body::Empty).await?;
+            return parse_json(response).await;
+        };
+        let key = self.cache_key(path, query);
+        let ttl = self.inner.cache_policy.ttl_for(path);
+        let body = fetch_cached(cache.as_ref(), &key, ttl, |headers| async move {
+            self.send_with_headers(Method::GET, path, query, Body::Empty, &headers, true)
+                .await
+        })
+        .await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
 
+    /// The cache key of a GET request: its URL and whose credentials it
+    /// was sent with.
+    fn cache_key(&self, path: &str, query: &[(&str, String)]) -> String {
+        let mut key = format!("GET {}{path}", self.inner.base_url);
+        for (i, (name, value)) in query.iter().enumerate() {
+            key.push(if i == 0 { '?' } else { '&' });
+            key.push_str(&percent_encode(name));
+            key.push('=');
+            key.push_str(&percent_encode(value));
+        }
+        match &self.inner.auth {
+            Some(auth) => key + " " + &auth.cache_identity(),
+            None => key,
+        }
+    }
+
+    /// Internal POST helper sending `body` as JSON.
+    async fn post<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        let body = serde_json::to_value(body)?;
+        let response = self
+            .send(Method::POST, path, &[], Body::Json(&body))
+            .await?;
+        parse_json(response).await
+    }
+
+    /// Internal POST helper for endpoints that create something, sending
+    /// `body` as JSON. See [`send_create`](Self::send_create).
+    async fn post_create<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        let body = serde_json::to_value(body)?;
+        let response = self.send_create(path, Body::Json(&body)).await?;
+        parse_json(response).await
+    }
+
+    /// Internal DELETE helper for endpoints that return no content.
+    async fn delete(&self, path: &str) -> Result<()> {
+        self.send(Method::DELETE, path, &[], Body::Empty).await?;
+        Ok(())
+    }
+
+    /// Send a request with auth, rate limiting and retries applied,
+    /// returning the response once its status has been checked.
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Body<'_>,
+    ) -> Result<reqwest::Response> {
+        self.send_with_headers(method, path, query, body, &HeaderMap::new(), true)
+            .await
+    }
+
+    /// Send a POST that creates something, such as a listing or an export
+    /// job. Repeating it after the server acted on it would create a second
+    /// one, so it is only retried if it was rate limited or the connection
+    /// failed before the request was sent.
+    async fn send_create(&self, path: &str, body: Body<'_>) -> Result<reqwest::Response> {
+        self.send_with_headers(Method::POST, path, &[], body, &HeaderMap::new(), false)
+            .await
+    }
+
+    /// [`send`](Self::send) with extra request headers. Requests that aren't
+    /// `idempotent` are only retried if the server didn't act on them.
+    async fn send_with_headers(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Body<'_>,
+        headers: &HeaderMap,
+        idempotent: bool,
+    ) -> Result<reqwest::Response> {
         let url = format!("{}{path}", self.inner.base_url);
 
-        let mut builder = self.inner.http.get(&url);
+        // Query and form parameters are both part of the OAuth signature.
+        let mut signed_params = query.to_vec();
+        if let Body::Form(form) = body {
+            signed_params.extend_from_slice(form);
+        }
+
+        let response = self
+            .execute(idempotent, || {
+                let mut builder = self
+                    .inner
+                    .http
+                    .request(method.clone(), &url)
+                    .headers(headers.clone());
+
+                if !query.is_empty() {
+                    builder = builder.query(query);
+                }
+
+                match body {
+                    Body::Empty => {}
+                    Body::Json(value) => builder = builder.json(value),
+                    Body::Form(form) => builder = builder.form(form),
+                    Body::Upload(csv) => {
+                        let part = Part::bytes(csv.to_vec())
+                            .file_name("inventory.csv")
+                            .mime_str("text/csv")?;
+                        builder = builder.multipart(Form::new().part("upload", part));
+                    }
+                }
+
+                // Apply authentication. Each attempt is signed with a fresh nonce.
+                if let Some(ref auth) = self.inner.auth {
+                    builder = auth.apply(builder, method.as_str(), &url, &signed_params);
+                }
 
-        if !query.is_empty() {
-            builder = builder.query(query);
+                Ok(builder)
+            })
+            .await?;
+
+        // A write makes cached copies of the resource, and of the resources
+        // above and below it, stale. They are removed once it has succeeded,
+        // so that a GET answered while it was in flight doesn't put the old
+        // body back.
+        if method != Method::GET {
+            if let Some(cache) = &self.inner.cache {
+                let prefix = format!("GET {}", self.inner.base_url);
+                cache.remove_matching(&|key| {
+                    key.strip_prefix(&prefix)
+                        .and_then(|rest| rest.split(['?', ' ']).next())
+                        .is_some_and(|cached| is_within(cached, path) || is_within(path, cached))
+                });
+            }
         }
+        Ok(response)
+    }
 
-        // Apply authentication
-        if let Some(ref auth) = self.inner.auth {
-            // For OAuth, we need the full URL with query params for signing.
-            // Build the full URL first.
-            let full_url = if !query.is_empty() {
-                let params: String = query
-                    .iter()
-                    .map(|(k, v)| format!("{k}={v}"))
-                    .collect::<Vec<_>>()
-                    .join("&");
-                format!("{url}?{params}")
-            } else {
-                url.clone()
+    /// Send the request made by `build`, waiting on the rate limiter before
+    /// each attempt and retrying according to the client's retry policy.
+    async fn execute(
+        &self,
+        idempotent: bool,
+        build: impl Fn() -> Result<RequestBuilder>,
+    ) -> Result<reqwest::Response> {
+        let policy = self.inner.retry_policy.as_ref();
+        let mut attempt = 1;
+        loop {
+            self.inner.rate_limiter.acquire().await;
+
+            let response = match build()?.send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    let delay = policy
+                        .filter(|p| p.retries_error(&e) && (idempotent || e.is_connect()))
+                        .and_then(|p| p.delay(attempt, None));
+                    let Some(delay) = delay else {
+                        return Err(e.into());
+                    };
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                }
             };
-            builder = auth.apply(builder, "GET", &full_url);
-        }
 
-        let response = builder.send().await?;
-
-        // Sync rate limiter with server headers
-        if let (Some(used), Some(limit)) = (
-            response
-                .headers()
-                .get("X-Discogs-Ratelimit-Used")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| v.parse::<u32>().ok()),
-            response
-                .headers()
-                .get("X-Discogs-Ratelimit")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| v.parse::<u32>().ok()),
-        ) {
-            self.inner.rate_limiter.sync_from_headers(used, limit).await;
-        }
+            // Sync rate limiter with server headers
+            if let (Some(used), Some(limit)) = (
+                response
+                    .headers()
+                    .get("X-Discogs-Ratelimit-Used")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u32>().ok()),
+                response
+                    .headers()
+                    .get("X-Discogs-Ratelimit")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u32>().ok()),
+            ) {
+                self.inner.rate_limiter.sync_from_headers(used, limit).await;
+            }
 
-        let status = response.status();
+            let status = response.status();
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                self.inner.rate_limiter.drain().await;
+            }
 
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            return Err(DiscogsError::RateLimited);
-        }
+            // A rate-limited request was never processed, so it's always
+            // safe to repeat.
+            let delay = policy
+                .filter(|p| {
+                    p.retries_status(status)
+                        && (idempotent || status == StatusCode::TOO_MANY_REQUESTS)
+                })
+                .and_then(|p| p.delay(attempt, retry_after(&response)));
+            if let Some(delay) = delay {
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
 
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            return Err(DiscogsError::Api {
-                status: status.as_u16(),
-                body,
-            });
+            return check_status(response).await;
         }
+    }
 
-        let body = response.text().await?;
-        let parsed: T = serde_json::from_str(&body)?;
-        Ok(parsed)
+    /// Return `AuthRequired` unless the client was built with credentials.
+    fn require_auth(&self) -> Result<()> {
+        if self.inner.auth.is_none() {
+            return Err(DiscogsError::AuthRequired);
+        }
+        Ok(())
     }
 
     /// Helper for paginated GET requests.
@@ -223,6 +462,96 @@ impl DiscogsClient {
         self.get(&format!("/releases/{id}"), &[]).await
     }
 
+    /// Get a release with marketplace prices in the given currency.
+    pub async fn release_with_currency(&self, id: u64, currency: Currency) -> Result<Release> {
+        let mut release: Release = self
+            .get(
+                &format!("/releases/{id}"),
+                &[("curr_abbr", currency.to_string())],
+            )
+            .await?;
+        // The price comes back as a bare number; record which currency it is in.
+        if let Some(ref mut price) = release.lowest_price {
+            price.currency = Some(currency);
+        }
+        Ok(release)
+    }
+
+    /// Get how many users have and want a release.
+    pub async fn release_stats(&self, release_id: u64) -> Result<ReleaseStats> {
+        self.get(&format!("/releases/{release_id}/stats"), &[])
+            .await
+    }
+
+    /// Get have/want stats for many releases, keyed by release ID.
+    ///
+    /// Requests are paced by the client's rate limiter, so large batches
+    /// take as long as the limit requires rather than failing with 429s.
+    /// Each release gets its own result, so one missing release does not
+    /// fail the batch.
+    pub async fn release_stats_batch(
+        &self,
+        release_ids: &[u64],
+    ) -> BTreeMap<u64, Result<ReleaseStats>> {
+        let mut ids = release_ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+
+        stream::iter(ids)
+            .map(|id| async move { (id, self.release_stats(id).await) })
+            .buffer_unordered(BATCH_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Get the community rating of a release.
+    pub async fn release_rating(&self, release_id: u64) -> Result<ReleaseRating> {
+        self.get(&format!("/releases/{release_id}/rating"), &[])
+            .await
+    }
+
+    /// Get a user's rating of a release.
+    pub async fn user_release_rating(
+        &self,
+        release_id: u64,
+        username: &str,
+    ) -> Result<UserReleaseRating> {
+        self.get(&format!("/releases/{release_id}/rating/{username}"), &[])
+            .await
+    }
+
+    /// Set the authenticated user's rating of a release, from 1 to 5.
+    pub async fn put_user_release_rating(
+        &self,
+        release_id: u64,
+        username: &str,
+        rating: u8,
+    ) -> Result<UserReleaseRating> {
+        self.require_auth()?;
+        if !(1..=5).contains(&rating) {
+            return Err(DiscogsError::Configuration(format!(
+                "rating must be between 1 and 5, got {rating}"
+            )));
+        }
+        let body = serde_json::json!({ "rating": rating });
+        let response = self
+            .send(
+                Method::PUT,
+                &format!("/releases/{release_id}/rating/{username}"),
+                &[],
+                Body::Json(&body),
+            )
+            .await?;
+        parse_json(response).await
+    }
+
+    /// Remove the authenticated user's rating of a release.
+    pub async fn delete_user_release_rating(&self, release_id: u64, username: &str) -> Result<()> {
+        self.require_auth()?;
+        self.delete(&format!("/releases/{release_id}/rating/{username}"))
+            .await
+    }
+
     /// Get a label by ID.
     pub async fn label(&self, id: u64) -> Result<Label> {
         self.get(&format!("/labels/{id}"), &[]).await
@@ -259,36 +588,645 @@ impl DiscogsClient {
         params: &SearchParams,
         pagination: &PaginationParams,
     ) -> Result<Paginated<SearchResult>> {
-        if self.inner.auth.is_none() {
-            return Err(DiscogsError::AuthRequired);
-        }
+        self.require_auth()?;
 
         self.get_paginated("/database/search", pagination, &params.as_query_pairs())
             .await
     }
 
-    /// Download an image from a Discogs image URL, returning the raw bytes.
-    pub async fn download_image(&self, url: &str) -> Result<Bytes> {
-        self.inner.rate_limiter.acquire().await;
+    /// Get the identity of the authenticated user.
+    pub async fn identity(&self) -> Result<Identity> {
+        self.require_auth()?;
+        self.get("/oauth/identity", &[]).await
+    }
+
+    /// Get a user's profile.
+    pub async fn user_profile(&self, username: &str) -> Result<UserProfile> {
+        self.get(&format!("/users/{username}"), &[]).await
+    }
+
+    /// Edit the authenticated user's profile.
+    pub async fn edit_user_profile(
+        &self,
+        username: &str,
+        edit: &ProfileEdit,
+    ) -> Result<UserProfile> {
+        self.require_auth()?;
+        self.post(&format!("/users/{username}"), edit).await
+    }
 
-        let response = self.inner.http.get(url).send().await?;
-        let status = response.status();
+    /// Get the releases a user has contributed to.
+    pub async fn contributions(
+        &self,
+        username: &str,
+        params: &ContributionsParams,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<Release>> {
+        self.get_paginated(
+            &format!("/users/{username}/contributions"),
+            pagination,
+            &params.as_query_pairs(),
+        )
+        .await
+    }
 
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            return Err(DiscogsError::RateLimited);
+    /// Get the artists, labels and releases a user has submitted edits to.
+    ///
+    /// Each page's submissions are returned artists first, then labels,
+    /// then releases.
+    pub async fn submissions(
+        &self,
+        username: &str,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<Submission>> {
+        let response: SubmissionsResponse = self
+            .get(
+                &format!("/users/{username}/submissions"),
+                &pagination.as_query_pairs(),
+            )
+            .await?;
+        Ok(Paginated::new(
+            response.submissions.into_vec(),
+            response.pagination,
+        ))
+    }
+
+    /// List the folders in a user's collection.
+    ///
+    /// Only public folders are returned unless authenticated as the owner.
+    pub async fn collection_folders(&self, username: &str) -> Result<Vec<CollectionFolder>> {
+        let response: FoldersResponse = self
+            .get(&format!("/users/{username}/collection/folders"), &[])
+            .await?;
+        Ok(response.folders)
+    }
+
+    /// Get a single collection folder.
+    pub async fn collection_folder(
+        &self,
+        username: &str,
+        folder_id: u64,
+    ) -> Result<CollectionFolder> {
+        self.get(
+            &format!("/users/{username}/collection/folders/{folder_id}"),
+            &[],
+        )
+        .await
+    }
+
+    /// Create a new collection folder.
+    pub async fn create_collection_folder(
+        &self,
+        username: &str,
+        name: &str,
+    ) -> Result<CollectionFolder> {
+        self.require_auth()?;
+        self.post_create(
+            &format!("/users/{username}/collection/folders"),
+            &serde_json::json!({ "name": name }),
+        )
+        .await
+    }
+
+    /// Rename a collection folder.
+    pub async fn rename_collection_folder(
+        &self,
+        username: &str,
+        folder_id: u64,
+        name: &str,
+    ) -> Result<CollectionFolder> {
+        self.require_auth()?;
+        self.post(
+            &format!("/users/{username}/collection/folders/{folder_id}"),
+            &serde_json::json!({ "name": name }),
+        )
+        .await
+    }
+
+    /// Delete a collection folder. The folder must be empty.
+    pub async fn delete_collection_folder(&self, username: &str, folder_id: u64) -> Result<()> {
+        self.require_auth()?;
+        self.delete(&format!("/users/{username}/collection/folders/{folder_id}"))
+            .await
+    }
+
+    /// Get the items in a collection folder. Folder `0` lists the whole collection.
+    pub async fn collection_items(
+        &self,
+        username: &str,
+        folder_id: u64,
+        params: &CollectionItemsParams,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<CollectionItem>> {
+        self.get_paginated(
+            &format!("/users/{username}/collection/folders/{folder_id}/releases"),
+            pagination,
+            &params.as_query_pairs(),
+        )
+        .await
+    }
+
+    /// List the instances of a release in a user's collection, across all folders.
+    pub async fn collection_items_by_release(
+        &self,
+        username: &str,
+        release_id: u64,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<CollectionItem>> {
+        self.get_paginated(
+            &format!("/users/{username}/collection/releases/{release_id}"),
+            pagination,
+            &[],
+        )
+        .await
+    }
+
+    /// Add a release to a collection folder, returning the new instance.
+    ///
+    /// Releases cannot be added to folder `0` ("All"); use folder `1` instead.
+    pub async fn add_to_collection(
+        &self,
+        username: &str,
+        folder_id: u64,
+        release_id: u64,
+    ) -> Result<CollectionInstance> {
+        self.require_auth()?;
+        if folder_id == 0 {
+            return Err(DiscogsError::Configuration(
+                "cannot add releases to the \"All\" folder".into(),
+            ));
         }
+        let response = self
+            .send_create(
+                &format!("/users/{username}/collection/folders/{folder_id}/releases/{release_id}"),
+                Body::Empty,
+            )
+            .await?;
+        parse_json(response).await
+    }
 
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            return Err(DiscogsError::Api {
-                status: status.as_u16(),
-                body,
-            });
+    /// Change the rating or folder of a collection instance.
+    pub async fn edit_collection_instance(
+        &self,
+        username: &str,
+        folder_id: u64,
+        release_id: u64,
+        instance_id: u64,
+        edit: &InstanceEdit,
+    ) -> Result<()> {
+        self.require_auth()?;
+        let form = edit.as_form_pairs()?;
+        self.send(
+            Method::POST,
+            &format!(
+                "/users/{username}/collection/folders/{folder_id}/releases/{release_id}/instances/{instance_id}"
+            ),
+            &[],
+            Body::Form(&form),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Remove an instance of a release from a collection folder.
+    pub async fn delete_collection_instance(
+        &self,
+        username: &str,
+        folder_id: u64,
+        release_id: u64,
+        instance_id: u64,
+    ) -> Result<()> {
+        self.require_auth()?;
+        self.delete(&format!(
+            "/users/{username}/collection/folders/{folder_id}/releases/{release_id}/instances/{instance_id}"
+        ))
+        .await
+    }
+
+    /// List the custom notes fields defined for a user's collection.
+    ///
+    /// Private fields are only returned when authenticated as the owner.
+    pub async fn collection_fields(&self, username: &str) -> Result<Vec<CollectionField>> {
+        let response: FieldsResponse = self
+            .get(&format!("/users/{username}/collection/fields"), &[])
+            .await?;
+        Ok(response.fields)
+    }
+
+    /// Set the value of a custom notes field on a collection instance.
+    pub async fn edit_instance_field(
+        &self,
+        username: &str,
+        folder_id: u64,
+        release_id: u64,
+        instance_id: u64,
+        field_id: u64,
+        value: &str,
+    ) -> Result<()> {
+        self.require_auth()?;
+        self.send(
+            Method::POST,
+            &format!(
+                "/users/{username}/collection/folders/{folder_id}/releases/{release_id}/instances/{instance_id}/fields/{field_id}"
+            ),
+            &[("value", value.to_string())],
+            Body::Empty,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Get the estimated minimum, median and maximum value of a user's collection.
+    pub async fn collection_value(&self, username: &str) -> Result<CollectionValue> {
+        self.require_auth()?;
+        self.get(&format!("/users/{username}/collection/value"), &[])
+            .await
+    }
+
+    /// Get a user's wantlist.
+    pub async fn wantlist(
+        &self,
+        username: &str,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<Want>> {
+        self.get_paginated(&format!("/users/{username}/wants"), pagination, &[])
+            .await
+    }
+
+    /// Add a release to a user's wantlist.
+    pub async fn add_want(&self, username: &str, release_id: u64, edit: &WantEdit) -> Result<Want> {
+        self.require_auth()?;
+        let query = edit.as_query_pairs()?;
+        let response = self
+            .send(
+                Method::PUT,
+                &format!("/users/{username}/wants/{release_id}"),
+                &query,
+                Body::Empty,
+            )
+            .await?;
+        parse_json(response).await
+    }
+
+    /// Change the notes or rating of a wantlist entry.
+    pub async fn edit_want(
+        &self,
+        username: &str,
+        release_id: u64,
+        edit: &WantEdit,
+    ) -> Result<Want> {
+        self.require_auth()?;
+        let query = edit.as_query_pairs()?;
+        let response = self
+            .send(
+                Method::POST,
+                &format!("/users/{username}/wants/{release_id}"),
+                &query,
+                Body::Empty,
+            )
+            .await?;
+        parse_json(response).await
+    }
+
+    /// Remove a release from a user's wantlist.
+    pub async fn delete_want(&self, username: &str, release_id: u64) -> Result<()> {
+        self.require_auth()?;
+        self.delete(&format!("/users/{username}/wants/{release_id}"))
+            .await
+    }
+
+    /// Get a user's public lists, or all of them for the authenticated user.
+    pub async fn user_lists(
+        &self,
+        username: &str,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<ListSummary>> {
+        self.get_paginated(&format!("/users/{username}/lists"), pagination, &[])
+            .await
+    }
+
+    /// Get a list and its items.
+    pub async fn list(&self, list_id: u64) -> Result<List> {
+        self.get(&format!("/lists/{list_id}"), &[]).await
+    }
+
+    /// Browse a seller's inventory.
+    pub async fn user_inventory(
+        &self,
+        username: &str,
+        params: &InventoryParams,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<Listing>> {
+        self.get_paginated(
+            &format!("/users/{username}/inventory"),
+            pagination,
+            &params.as_query_pairs(),
+        )
+        .await
+    }
+
+    /// Get a marketplace listing by ID.
+    pub async fn listing(&self, listing_id: u64) -> Result<Listing> {
+        self.get(&format!("/marketplace/listings/{listing_id}"), &[])
+            .await
+    }
+
+    /// Create a marketplace listing.
+    ///
+    /// The listing is validated locally first, returning
+    /// `DiscogsError::Configuration` for values the API would reject.
+    pub async fn create_listing(&self, params: &ListingParams) -> Result<CreatedListing> {
+        self.require_auth()?;
+        params.validate()?;
+        self.post_create("/marketplace/listings", params).await
+    }
+
+    /// Replace the details of an existing marketplace listing.
+    pub async fn edit_listing(&self, listing_id: u64, params: &ListingParams) -> Result<()> {
+        self.require_auth()?;
+        params.validate()?;
+        let body = serde_json::to_value(params)?;
+        self.send(
+            Method::POST,
+            &format!("/marketplace/listings/{listing_id}"),
+            &[],
+            Body::Json(&body),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Delete a marketplace listing.
+    pub async fn delete_listing(&self, listing_id: u64) -> Result<()> {
+        self.require_auth()?;
+        self.delete(&format!("/marketplace/listings/{listing_id}"))
+            .await
+    }
+
+    /// Get marketplace statistics for a release.
+    pub async fn marketplace_stats(&self, release_id: u64) -> Result<MarketplaceStats> {
+        self.get(&format!("/marketplace/stats/{release_id}"), &[])
+            .await
+    }
+
+    /// Calculate the fee Discogs would charge on a sale at `price`.
+    pub async fn marketplace_fee(&self, price: f64, currency: Currency) -> Result<Money> {
+        if !(price.is_finite() && price > 0.0) {
+            return Err(DiscogsError::Configuration(format!(
+                "price must be positive, got {price}"
+            )));
         }
+        self.get(&format!("/marketplace/fee/{price:.2}/{currency}"), &[])
+            .await
+    }
 
+    /// Get suggested prices for a release in each media condition, in the
+    /// authenticated seller's currency.
+    ///
+    /// Grades not known to this crate are left out, rather than all being
+    /// keyed by `Condition::Other`.
+    pub async fn price_suggestions(&self, release_id: u64) -> Result<BTreeMap<Condition, Money>> {
+        self.require_auth()?;
+        let suggestions: BTreeMap<String, Money> = self
+            .get(&format!("/marketplace/price_suggestions/{release_id}"), &[])
+            .await?;
+        Ok(suggestions
+            .into_iter()
+            .filter_map(|(label, price)| {
+                let condition = serde_json::from_value(serde_json::Value::String(label)).ok()?;
+                (condition != Condition::Other).then_some((condition, price))
+            })
+            .collect())
+    }
+
+    /// List the authenticated seller's orders.
+    pub async fn orders(
+        &self,
+        params: &OrderParams,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<Order>> {
+        self.require_auth()?;
+        self.get_paginated("/marketplace/orders", pagination, &params.as_query_pairs())
+            .await
+    }
+
+    /// Get a marketplace order by ID.
+    pub async fn order(&self, order_id: &str) -> Result<Order> {
+        self.require_auth()?;
+        self.get(&format!("/marketplace/orders/{order_id}"), &[])
+            .await
+    }
+
+    /// Change an order's status or shipping cost, returning the updated order.
+    ///
+    /// The update is checked against the order's current status first,
+    /// returning `DiscogsError::Configuration` for illegal transitions.
+    pub async fn edit_order(&self, order: &Order, update: &OrderUpdate) -> Result<Order> {
+        self.require_auth()?;
+        update.validate_for(order)?;
+        self.post(&format!("/marketplace/orders/{}", order.id), update)
+            .await
+    }
+
+    /// Get the message thread of an order, newest first.
+    pub async fn order_messages(
+        &self,
+        order_id: &str,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<OrderMessage>> {
+        self.require_auth()?;
+        self.get_paginated(
+            &format!("/marketplace/orders/{order_id}/messages"),
+            pagination,
+            &[],
+        )
+        .await
+    }
+
+    /// Add a message to an order's thread, optionally changing its status
+    /// at the same time. At least one of `message` and `status` is required.
+    pub async fn post_order_message(
+        &self,
+        order_id: &str,
+        message: Option<&str>,
+        status: Option<OrderStatus>,
+    ) -> Result<OrderMessage> {
+        self.require_auth()?;
+        if message.is_none() && status.is_none() {
+            return Err(DiscogsError::Configuration(
+                "an order message needs a message or a status".into(),
+            ));
+        }
+        if status == Some(OrderStatus::Other) {
+            return Err(DiscogsError::Configuration(
+                "orders can't be moved to an unknown status".into(),
+            ));
+        }
+        self.post_create(
+            &format!("/marketplace/orders/{order_id}/messages"),
+            &OrderMessageBody { message, status },
+        )
+        .await
+    }
+
+    /// Start exporting the authenticated seller's inventory to CSV,
+    /// returning the export ID.
+    pub async fn request_inventory_export(&self) -> Result<u64> {
+        self.require_auth()?;
+        let response = self.send_create("/inventory/export", Body::Empty).await?;
+        job_id_from_location(&response)
+    }
+
+    /// List the authenticated seller's recent inventory exports.
+    pub async fn inventory_exports(
+        &self,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<InventoryExport>> {
+        self.require_auth()?;
+        self.get_paginated("/inventory/export", pagination, &[])
+            .await
+    }
+
+    /// Get the status of an inventory export.
+    pub async fn inventory_export(&self, export_id: u64) -> Result<InventoryExport> {
+        self.require_auth()?;
+        self.get(&format!("/inventory/export/{export_id}"), &[])
+            .await
+    }
+
+    /// Download the CSV of a finished inventory export.
+    pub async fn download_inventory_export(&self, export_id: u64) -> Result<Bytes> {
+        self.require_auth()?;
+        let response = self
+            .send(
+                Method::GET,
+                &format!("/inventory/export/{export_id}/download"),
+                &[],
+                Body::Empty,
+            )
+            .await?;
         Ok(response.bytes().await?)
     }
 
+    /// Export the authenticated seller's inventory: request an export, wait
+    /// for it to finish, then download and parse the CSV.
+    ///
+    /// Returns `DiscogsError::JobFailed` if Discogs reports the export as
+    /// failed, or `DiscogsError::JobTimeout` if it is still running after
+    /// `options.timeout`.
+    pub async fn export_inventory(&self, options: &PollOptions) -> Result<Vec<InventoryExportRow>> {
+        let export_id = self.request_inventory_export().await?;
+        poll_job(
+            export_id,
+            options,
+            || self.inventory_export(export_id),
+            |export| export.status,
+        )
+        .await?;
+        let data = self.download_inventory_export(export_id).await?;
+        InventoryExportRow::from_csv(&data)
+    }
+
+    /// Upload new listings in bulk, returning the upload ID.
+    pub async fn upload_inventory_add(&self, listings: &[UploadListing]) -> Result<u64> {
+        self.require_auth()?;
+        let csv = UploadListing::to_csv(listings)?;
+        self.upload_inventory("add", &csv).await
+    }
+
+    /// Change existing listings in bulk, returning the upload IDs.
+    ///
+    /// Changes setting different fields are sent as separate uploads, one per
+    /// set of fields, so that no listing has a field it didn't set cleared.
+    /// If an upload fails after others were accepted, the error is
+    /// `DiscogsError::PartialUpload`, carrying the accepted upload IDs.
+    pub async fn upload_inventory_change(&self, changes: &[ListingChange]) -> Result<Vec<u64>> {
+        self.require_auth()?;
+        let mut upload_ids = Vec::new();
+        for csv in ListingChange::to_csv(changes)? {
+            match self.upload_inventory("change", &csv).await {
+                Ok(upload_id) => upload_ids.push(upload_id),
+                Err(e) if upload_ids.is_empty() => return Err(e),
+                Err(e) => {
+                    return Err(DiscogsError::PartialUpload {
+                        upload_ids,
+                        source: Box::new(e),
+                    })
+                }
+            }
+        }
+        Ok(upload_ids)
+    }
+
+    /// Delete listings in bulk, returning the upload ID.
+    pub async fn upload_inventory_delete(&self, listing_ids: &[u64]) -> Result<u64> {
+        self.require_auth()?;
+        let csv = delete_csv(listing_ids)?;
+        self.upload_inventory("delete", &csv).await
+    }
+
+    /// Send an inventory upload CSV and return the upload ID.
+    async fn upload_inventory(&self, kind: &str, csv: &[u8]) -> Result<u64> {
+        let response = self
+            .send_create(&format!("/inventory/upload/{kind}"), Body::Upload(csv))
+            .await?;
+        job_id_from_location(&response)
+    }
+
+    /// List the authenticated seller's recent inventory uploads.
+    pub async fn inventory_uploads(
+        &self,
+        pagination: &PaginationParams,
+    ) -> Result<Paginated<InventoryUpload>> {
+        self.require_auth()?;
+        self.get_paginated("/inventory/upload", pagination, &[])
+            .await
+    }
+
+    /// Get the status of an inventory upload.
+    pub async fn inventory_upload(&self, upload_id: u64) -> Result<InventoryUpload> {
+        self.require_auth()?;
+        self.get(&format!("/inventory/upload/{upload_id}"), &[])
+            .await
+    }
+
+    /// Wait for an inventory upload to finish, returning it with its results.
+    ///
+    /// Returns `DiscogsError::JobFailed` if Discogs reports the upload as
+    /// failed, or `DiscogsError::JobTimeout` if it is still running after
+    /// `options.timeout`.
+    pub async fn wait_for_inventory_upload(
+        &self,
+        upload_id: u64,
+        options: &PollOptions,
+    ) -> Result<InventoryUpload> {
+        poll_job(
+            upload_id,
+            options,
+            || self.inventory_upload(upload_id),
+            |upload| upload.status,
+        )
+        .await
+    }
+
+    /// Download an image from a Discogs image URL, returning the raw bytes.
+    pub async fn download_image(&self, url: &str) -> Result<Bytes> {
+        let Some(cache) = &self.inner.cache else {
+            let response = self.execute(true, || Ok(self.inner.http.get(url))).await?;
+            return Ok(response.bytes().await?);
+        };
+        // Images aren't tied to credentials, so the key is just the URL.
+        let key = format!("GET {url}");
+        let ttl = self.inner.cache_policy.image_ttl;
+        fetch_cached(cache.as_ref(), &key, ttl, |headers| async move {
+            self.execute(true, || {
+                Ok(self.inner.http.get(url).headers(headers.clone()))
+            })
+            .await
+        })
+        .await
+    }
+
     /// Fetch a release and download its primary cover image.
     ///
     /// Returns `None` if the release has no images. Prefers the "primary"
@@ -313,6 +1251,193 @@ impl DiscogsClient {
     }
 }
 
+/// Deserialize a JSON response body.
+async fn parse_json<T: DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+    let body = response.text().await?;
+    let parsed: T = serde_json::from_str(&body)?;
+    Ok(parsed)
+}
+
+/// Fetch a response body through `cache`. Fresh entries are returned
+/// without calling `fetch`; stale ones are revalidated with `If-None-Match`
+/// or `If-Modified-Since` when they have validators.
+async fn fetch_cached<F, Fut>(
+    cache: &dyn Cache,
+    key: &str,
+    ttl: Duration,
+    fetch: F,
+) -> Result<Bytes>
+where
+    F: FnOnce(HeaderMap) -> Fut,
+    Fut: Future<Output = Result<reqwest::Response>>,
+{
+    let cached = cache.get(key);
+    if let Some(entry) = cached.as_ref().filter(|entry| entry.is_fresh()) {
+        return Ok(entry.body.clone());
+    }
+
+    let mut headers = HeaderMap::new();
+    if let Some(entry) = &cached {
+        let validators = [
+            (IF_NONE_MATCH, &entry.etag),
+            (IF_MODIFIED_SINCE, &entry.last_modified),
+        ];
+        for (name, value) in validators {
+            if let Some(value) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(name, value);
+            }
+        }
+    }
+
+    let response = fetch(headers).await?;
+    let expires_at = expiry_after(ttl);
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        let Some(mut entry) = cached else {
+            return Err(DiscogsError::Api {
+                status: StatusCode::NOT_MODIFIED.as_u16(),
+                body: String::new(),
+            });
+        };
+        entry.expires_at = expires_at;
+        cache.put(key, entry.clone());
+        return Ok(entry.body);
+    }
+
+    let entry = CacheEntry {
+        etag: header_string(&response, ETAG),
+        last_modified: header_string(&response, LAST_MODIFIED),
+        body: response.bytes().await?,
+        expires_at,
+    };
+    if entry.is_fresh() || entry.has_validators() {
+        cache.put(key, entry.clone());
+    }
+    Ok(entry.body)
+}
+
+/// Whether `path` is `ancestor` or a path below it, e.g. `/releases/1/rating`
+/// within `/releases/1`.
+fn is_within(path: &str, ancestor: &str) -> bool {
+    path.strip_prefix(ancestor)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Read a response header as a string, if present and valid.
+fn header_string(response: &reqwest::Response, name: HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Read the job ID from the `Location` header Discogs returns when an
+/// inventory job is created, e.g. `.../inventory/export/599632`.
+fn job_id_from_location(response: &reqwest::Response) -> Result<u64> {
+    response
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|location| location.trim_end_matches('/').rsplit('/').next())
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| DiscogsError::Api {
+            status: response.status().as_u16(),
+            body: "response has no job Location header".into(),
+        })
+}
+
+/// Fetch a job with exponential backoff until it finishes, returning the
+/// finished job.
+async fn poll_job<T, F, Fut>(
+    id: u64,
+    options: &PollOptions,
+    mut fetch: F,
+    status: impl Fn(&T) -> JobStatus,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    // A timeout too large to represent means there is no deadline.
+    let deadline = tokio::time::Instant::now().checked_add(options.timeout);
+    let mut interval = options.initial_interval;
+    loop {
+        let job = fetch().await?;
+        match status(&job) {
+            JobStatus::Success => return Ok(job),
+            JobStatus::Failure => return Err(DiscogsError::JobFailed { id }),
+            _ => {}
+        }
+        let mut delay = interval;
+        if let Some(deadline) = deadline {
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return Err(DiscogsError::JobTimeout { id });
+            }
+            delay = delay.min(deadline - now);
+        }
+        tokio::time::sleep(delay).await;
+        interval = interval.saturating_mul(2).min(options.max_interval);
+    }
+}
+
+/// Map rate-limit and non-success responses to errors, passing successful ones through.
+pub(crate) async fn check_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        return Err(DiscogsError::RateLimited);
+    }
+
+    // Not Modified only answers conditional requests, which the caller handles.
+    if !status.is_success() && status != StatusCode::NOT_MODIFIED {
+        let body = response.text().await.unwrap_or_default();
+        return Err(DiscogsError::Api {
+            status: status.as_u16(),
+            body,
+        });
+    }
+
+    Ok(response)
+}
+
+/// Request body for [`DiscogsClient::send`].
+#[derive(Clone, Copy)]
+enum Body<'a> {
+    Empty,
+    Json(&'a serde_json::Value),
+    /// Form-encoded parameters, which are included in the OAuth signature.
+    Form(&'a [(&'a str, String)]),
+    /// A CSV file sent as the `upload` field of a multipart form.
+    Upload(&'a [u8]),
+}
+
+#[derive(Serialize)]
+struct OrderMessageBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<OrderStatus>,
+}
+
+#[derive(Deserialize)]
+struct SubmissionsResponse {
+    pagination: PaginationInfo,
+    #[serde(default)]
+    submissions: SubmissionGroups,
+}
+
+#[derive(Deserialize)]
+struct FoldersResponse {
+    folders: Vec<CollectionFolder>,
+}
+
+#[derive(Deserialize)]
+struct FieldsResponse {
+    fields: Vec<CollectionField>,
+}
+
 /// Downloaded cover art image.
 pub struct CoverArt {
     /// Raw image bytes (typically JPEG).
@@ -353,9 +1478,7 @@ mod tests {
 
     #[test]
     fn builder_succeeds_with_user_agent() {
-        let client = DiscogsClient::builder()
-            .user_agent("TestApp/1.0")
-            .build();
+        let client = DiscogsClient::builder().user_agent("TestApp/1.0").build();
         assert!(client.is_ok());
     }
 
@@ -15,8 +15,31 @@ pub enum DiscogsError {
     #[error("authentication required for this endpoint")]
     AuthRequired,
 
+    #[error("OAuth error: {0}")]
+    OAuth(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("JSON deserialization error: {0}")]
     Deserialization(#[from] serde_json::Error),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("inventory job {id} failed")]
+    JobFailed { id: u64 },
+
+    #[error("timed out waiting for inventory job {id}")]
+    JobTimeout { id: u64 },
+
+    /// Some uploads of a batch were accepted before one failed. The accepted
+    /// ones are still applied and can be polled by their IDs.
+    #[error("inventory upload failed after uploads {upload_ids:?} were accepted: {source}")]
+    PartialUpload {
+        upload_ids: Vec<u64>,
+        source: Box<DiscogsError>,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, DiscogsError>;
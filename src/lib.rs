@@ -1,13 +1,29 @@
 mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod cache;
 mod client;
 mod error;
+#[cfg(feature = "oauth-loopback")]
+mod loopback;
 mod models;
+mod oauth;
 mod pagination;
 mod rate_limit;
-#[cfg(feature = "blocking")]
-pub mod blocking;
+mod retry;
+#[cfg(feature = "sqlite-cache")]
+mod sqlite_cache;
+mod stream;
 
+pub use cache::{Cache, CacheEntry, CachePolicy, FileCache, MemoryCache};
 pub use client::{ClientBuilder, CoverArt, DiscogsClient};
 pub use error::DiscogsError;
+#[cfg(feature = "oauth-loopback")]
+pub use loopback::{LoopbackCallback, LoopbackOptions};
 pub use models::*;
+pub use oauth::{OAuthCredentials, OAuthFlow, OAuthFlowBuilder, RequestToken};
 pub use pagination::{Paginated, PaginationParams};
+pub use retry::RetryPolicy;
+#[cfg(feature = "sqlite-cache")]
+pub use sqlite_cache::SqliteCache;
+pub use stream::{PageStream, StreamOptions};
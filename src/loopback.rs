@@ -0,0 +1,241 @@
+//! Local loopback listener for capturing the OAuth verifier.
+//!
+//! Enabled with the `oauth-loopback` feature flag.
+
+use std::net::{Ipv4Addr, SocketAddr};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::Duration;
+
+use crate::error::{DiscogsError, Result};
+use crate::oauth::{form_value, parse_form, OAuthCredentials, OAuthFlow, RequestToken};
+
+/// Upper bound on the size of the redirect request we are willing to read.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Consecutive failed accepts after which the listener is considered broken.
+const MAX_ACCEPT_FAILURES: u32 = 10;
+
+const SUCCESS_PAGE: &str =
+    "<html><body><p>Authorization complete. You can close this window.</p></body></html>";
+const DENIED_PAGE: &str =
+    "<html><body><p>Authorization was denied. You can close this window.</p></body></html>";
+
+/// Options for the loopback callback listener.
+#[derive(Debug, Clone)]
+pub struct LoopbackOptions {
+    /// Port to bind on `127.0.0.1`. `0` picks a free port.
+    pub port: u16,
+    /// Path Discogs redirects to, e.g. `/callback`.
+    pub path: String,
+    /// How long to wait for the user to approve access.
+    pub timeout: Duration,
+    /// How long to wait for a connection to send its request before moving
+    /// on, e.g. past a browser's idle preconnect.
+    pub read_timeout: Duration,
+}
+
+impl Default for LoopbackOptions {
+    fn default() -> Self {
+        Self {
+            port: 0,
+            path: "/callback".to_string(),
+            timeout: Duration::from_secs(300),
+            read_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl LoopbackOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+}
+
+/// A localhost HTTP listener that receives the Discogs authorization redirect.
+pub struct LoopbackCallback {
+    listener: TcpListener,
+    callback_url: String,
+    path: String,
+    read_timeout: Duration,
+}
+
+impl LoopbackCallback {
+    /// Bind the listener on `127.0.0.1`.
+    pub async fn bind(options: &LoopbackOptions) -> Result<Self> {
+        let listener =
+            TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, options.port))).await?;
+        let path = if options.path.starts_with('/') {
+            options.path.clone()
+        } else {
+            format!("/{}", options.path)
+        };
+        let callback_url = format!("http://{}{path}", listener.local_addr()?);
+        Ok(Self {
+            listener,
+            callback_url,
+            path,
+            read_timeout: options.read_timeout,
+        })
+    }
+
+    /// The URL to pass as `oauth_callback`.
+    pub fn callback_url(&self) -> &str {
+        &self.callback_url
+    }
+
+    /// Wait for Discogs to redirect back with the verifier for `request_token`.
+    ///
+    /// Requests for other paths or other tokens are answered and ignored, as
+    /// are connections that fail or stay idle. Waits indefinitely; wrap in a
+    /// timeout as needed.
+    ///
+    /// A failed accept, e.g. when out of file descriptors, is retried after
+    /// a short delay. The error is returned if accepting keeps failing.
+    pub async fn wait_for_verifier(&self, request_token: &RequestToken) -> Result<String> {
+        let mut failures = 0;
+        loop {
+            let mut stream = match self.listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    failures += 1;
+                    if failures >= MAX_ACCEPT_FAILURES {
+                        return Err(e.into());
+                    }
+                    tokio::time::sleep(Duration::from_millis(10 << failures)).await;
+                    continue;
+                }
+            };
+            failures = 0;
+            if let Some(outcome) = self.handle(&mut stream, request_token).await {
+                return outcome;
+            }
+        }
+    }
+
+    /// Answer one connection, returning the outcome of the flow if it was
+    /// the redirect for `request_token`. Errors on other connections are
+    /// ignored, as is failing to send the page after the redirect, e.g.
+    /// because the browser tab was closed.
+    async fn handle(
+        &self,
+        stream: &mut TcpStream,
+        request_token: &RequestToken,
+    ) -> Option<Result<String>> {
+        let target =
+            match tokio::time::timeout(self.read_timeout, read_request_target(stream)).await {
+                Ok(Ok(Some(target))) => target,
+                Ok(Ok(None)) => {
+                    let _ = respond(stream, "400 Bad Request", "").await;
+                    return None;
+                }
+                Ok(Err(_)) | Err(_) => return None,
+            };
+
+        let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+        if path != self.path {
+            let _ = respond(stream, "404 Not Found", "").await;
+            return None;
+        }
+
+        let params = parse_form(query);
+        if form_value(&params, "denied") == Some(&request_token.token) {
+            let _ = respond(stream, "200 OK", DENIED_PAGE).await;
+            return Some(Err(DiscogsError::OAuth("authorization was denied".into())));
+        }
+
+        match (
+            form_value(&params, "oauth_token"),
+            form_value(&params, "oauth_verifier"),
+        ) {
+            (Some(token), Some(verifier)) if token == request_token.token => {
+                let _ = respond(stream, "200 OK", SUCCESS_PAGE).await;
+                Some(Ok(verifier.to_string()))
+            }
+            _ => {
+                let _ = respond(stream, "400 Bad Request", "").await;
+                None
+            }
+        }
+    }
+}
+
+impl OAuthFlow {
+    /// Run the whole authorization flow, capturing the verifier on a local
+    /// loopback listener instead of asking the user to paste it.
+    ///
+    /// `open_authorize_url` is called with the URL the user must visit,
+    /// e.g. to launch a browser or print it to the terminal.
+    pub async fn authorize_with_loopback<F>(
+        &self,
+        options: &LoopbackOptions,
+        open_authorize_url: F,
+    ) -> Result<OAuthCredentials>
+    where
+        F: FnOnce(&str),
+    {
+        let callback = LoopbackCallback::bind(options).await?;
+        let request_token = self.request_token(callback.callback_url()).await?;
+        open_authorize_url(&self.authorize_url(&request_token));
+
+        let verifier =
+            tokio::time::timeout(options.timeout, callback.wait_for_verifier(&request_token))
+                .await
+                .map_err(|_| {
+                    DiscogsError::OAuth("timed out waiting for the authorization redirect".into())
+                })??;
+
+        self.access_token(&request_token, &verifier).await
+    }
+}
+
+/// Read the request head and return its target (path and query).
+async fn read_request_target(stream: &mut TcpStream) -> Result<Option<String>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 || buf.len() + n > MAX_REQUEST_BYTES {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut parts = head.lines().next().unwrap_or("").split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => Ok(Some(target.to_string())),
+        _ => Ok(None),
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
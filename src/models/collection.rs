@@ -0,0 +1,384 @@
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::{BasicInformation, Money, SortOrder};
+use crate::error::{DiscogsError, Result};
+
+/// A folder in a user's collection.
+///
+/// Folder `0` ("All") and folder `1` ("Uncategorized") always exist.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CollectionFolder {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub count: u32,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+}
+
+/// A release instance in a collection folder.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CollectionItem {
+    /// The release ID.
+    pub id: u64,
+    pub instance_id: u64,
+    #[serde(default)]
+    pub folder_id: Option<u64>,
+    #[serde(default)]
+    pub rating: u8,
+    #[serde(default)]
+    pub date_added: Option<String>,
+    pub basic_information: BasicInformation,
+    /// Custom field values, keyed by [`CollectionField`] ID. Use
+    /// [`note`](Self::note) to read one according to its field's type.
+    #[serde(default, deserialize_with = "notes_by_field_id")]
+    pub notes: BTreeMap<u64, String>,
+}
+
+impl CollectionItem {
+    /// The value of `field` on this instance, or `None` if it isn't set.
+    pub fn note<'a>(&'a self, field: &CollectionField) -> Option<NoteValue<'a>> {
+        let value = self.notes.get(&field.id())?.as_str();
+        Some(match field {
+            CollectionField::Dropdown { options, .. } => NoteValue::Dropdown {
+                value,
+                option: options.iter().position(|option| option == value),
+            },
+            CollectionField::Textarea { .. } => NoteValue::Text(value),
+        })
+    }
+}
+
+/// A custom field value on a collection instance, typed by its
+/// [`CollectionField`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteValue<'a> {
+    /// The value of a dropdown field, with its index in the field's
+    /// options. The index is `None` if the options changed after the value
+    /// was set.
+    Dropdown {
+        value: &'a str,
+        option: Option<usize>,
+    },
+    /// The text of a textarea field.
+    Text(&'a str),
+}
+
+/// Deserialize `[{"field_id": 1, "value": "..."}]` into a map keyed by field ID.
+fn notes_by_field_id<'de, D>(
+    deserializer: D,
+) -> std::result::Result<BTreeMap<u64, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Note {
+        field_id: u64,
+        #[serde(default)]
+        value: String,
+    }
+
+    let notes: Option<Vec<Note>> = Option::deserialize(deserializer)?;
+    Ok(notes
+        .unwrap_or_default()
+        .into_iter()
+        .map(|note| (note.field_id, note.value))
+        .collect())
+}
+
+/// A user-defined notes field on collection instances.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CollectionField {
+    /// A field whose value is picked from a fixed list.
+    Dropdown {
+        id: u64,
+        name: String,
+        #[serde(default)]
+        position: u32,
+        #[serde(default)]
+        public: bool,
+        #[serde(default)]
+        options: Vec<String>,
+    },
+    /// A free-text field shown with the given number of lines.
+    Textarea {
+        id: u64,
+        name: String,
+        #[serde(default)]
+        position: u32,
+        #[serde(default)]
+        public: bool,
+        #[serde(default)]
+        lines: u32,
+    },
+}
+
+impl CollectionField {
+    pub fn id(&self) -> u64 {
+        match self {
+            CollectionField::Dropdown { id, .. } | CollectionField::Textarea { id, .. } => *id,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            CollectionField::Dropdown { name, .. } | CollectionField::Textarea { name, .. } => name,
+        }
+    }
+}
+
+/// A newly added collection instance.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CollectionInstance {
+    pub instance_id: u64,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+}
+
+/// Changes to apply to a collection instance.
+#[derive(Debug, Clone, Default)]
+pub struct InstanceEdit {
+    /// Rating from 1 to 5, or 0 to clear it.
+    pub rating: Option<u8>,
+    /// Folder to move the instance to.
+    pub folder_id: Option<u64>,
+}
+
+impl InstanceEdit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rating(mut self, r: u8) -> Self {
+        self.rating = Some(r);
+        self
+    }
+
+    pub fn folder_id(mut self, f: u64) -> Self {
+        self.folder_id = Some(f);
+        self
+    }
+
+    pub(crate) fn as_form_pairs(&self) -> Result<Vec<(&str, String)>> {
+        let mut pairs = Vec::new();
+        if let Some(rating) = self.rating {
+            if rating > 5 {
+                return Err(DiscogsError::Configuration(format!(
+                    "rating must be between 0 and 5, got {rating}"
+                )));
+            }
+            pairs.push(("rating", rating.to_string()));
+        }
+        if let Some(folder_id) = self.folder_id {
+            if folder_id == 0 {
+                return Err(DiscogsError::Configuration(
+                    "cannot move an instance to the \"All\" folder".into(),
+                ));
+            }
+            pairs.push(("folder_id", folder_id.to_string()));
+        }
+        if pairs.is_empty() {
+            return Err(DiscogsError::Configuration(
+                "instance edit has no changes".into(),
+            ));
+        }
+        Ok(pairs)
+    }
+}
+
+/// The estimated value of a user's collection.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CollectionValue {
+    pub minimum: Money,
+    pub median: Money,
+    pub maximum: Money,
+}
+
+/// Field to sort collection items by.
+#[derive(Debug, Clone)]
+pub enum CollectionSort {
+    Label,
+    Artist,
+    Title,
+    Catno,
+    Format,
+    Rating,
+    Added,
+    Year,
+}
+
+impl fmt::Display for CollectionSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollectionSort::Label => write!(f, "label"),
+            CollectionSort::Artist => write!(f, "artist"),
+            CollectionSort::Title => write!(f, "title"),
+            CollectionSort::Catno => write!(f, "catno"),
+            CollectionSort::Format => write!(f, "format"),
+            CollectionSort::Rating => write!(f, "rating"),
+            CollectionSort::Added => write!(f, "added"),
+            CollectionSort::Year => write!(f, "year"),
+        }
+    }
+}
+
+/// Parameters for listing the items in a collection folder.
+#[derive(Debug, Clone, Default)]
+pub struct CollectionItemsParams {
+    pub sort: Option<CollectionSort>,
+    pub sort_order: Option<SortOrder>,
+}
+
+impl CollectionItemsParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sort(mut self, s: CollectionSort) -> Self {
+        self.sort = Some(s);
+        self
+    }
+
+    pub fn sort_order(mut self, o: SortOrder) -> Self {
+        self.sort_order = Some(o);
+        self
+    }
+
+    pub(crate) fn as_query_pairs(&self) -> Vec<(&str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(ref s) = self.sort {
+            pairs.push(("sort", s.to_string()));
+        }
+        if let Some(ref o) = self.sort_order {
+            pairs.push(("sort_order", o.to_string()));
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Currency;
+
+    #[test]
+    fn test_deserialize_collection_item() {
+        let json = r#"{
+            "id": 2464521,
+            "instance_id": 1,
+            "folder_id": 1,
+            "rating": 4,
+            "date_added": "2014-07-29T10:31:43-07:00",
+            "basic_information": {
+                "id": 2464521,
+                "title": "Year Zero",
+                "year": 2007,
+                "artists": [{"id": 3857, "name": "Nine Inch Nails"}],
+                "formats": [{"name": "Vinyl", "qty": "2"}],
+                "labels": [{"id": 2311, "name": "Interscope Records", "catno": "B0008764-01"}]
+            }
+        }"#;
+        let item: CollectionItem = serde_json::from_str(json).unwrap();
+        assert_eq!(item.instance_id, 1);
+        assert_eq!(item.rating, 4);
+        assert_eq!(item.basic_information.title.as_deref(), Some("Year Zero"));
+        assert_eq!(item.basic_information.artists[0].name, "Nine Inch Nails");
+    }
+
+    #[test]
+    fn test_deserialize_collection_item_notes() {
+        let json = r#"{
+            "id": 1,
+            "instance_id": 2,
+            "basic_information": {"id": 1},
+            "notes": [
+                {"field_id": 1, "value": "Near Mint (NM or M-)"},
+                {"field_id": 3, "value": "Signed copy"}
+            ]
+        }"#;
+        let item: CollectionItem = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            item.notes.get(&1).map(String::as_str),
+            Some("Near Mint (NM or M-)")
+        );
+        assert_eq!(item.notes.get(&3).map(String::as_str), Some("Signed copy"));
+
+        let fields: Vec<CollectionField> = serde_json::from_str(
+            r#"[
+                {"id": 1, "name": "Media", "type": "dropdown",
+                 "options": ["Mint (M)", "Near Mint (NM or M-)"]},
+                {"id": 2, "name": "Sleeve", "type": "dropdown", "options": []},
+                {"id": 3, "name": "Notes", "type": "textarea"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            item.note(&fields[0]),
+            Some(NoteValue::Dropdown {
+                value: "Near Mint (NM or M-)",
+                option: Some(1)
+            })
+        );
+        assert_eq!(item.note(&fields[1]), None);
+        assert_eq!(item.note(&fields[2]), Some(NoteValue::Text("Signed copy")));
+    }
+
+    #[test]
+    fn test_deserialize_collection_fields() {
+        let json = r#"[
+            {"id": 1, "name": "Media", "type": "dropdown", "position": 1, "public": true,
+             "options": ["Mint (M)", "Near Mint (NM or M-)"]},
+            {"id": 3, "name": "Notes", "type": "textarea", "position": 3, "public": false,
+             "lines": 3}
+        ]"#;
+        let fields: Vec<CollectionField> = serde_json::from_str(json).unwrap();
+        assert!(
+            matches!(&fields[0], CollectionField::Dropdown { options, .. } if options.len() == 2)
+        );
+        assert!(matches!(
+            fields[1],
+            CollectionField::Textarea { lines: 3, .. }
+        ));
+        assert_eq!(fields[1].name(), "Notes");
+    }
+
+    #[test]
+    fn test_deserialize_collection_value() {
+        let json = r#"{"maximum": "$1,234.56", "median": "$650.00", "minimum": "$98.10"}"#;
+        let value: CollectionValue = serde_json::from_str(json).unwrap();
+        assert_eq!(value.maximum, Money::new(1234.56, Currency::Usd));
+        assert_eq!(value.minimum.amount, 98.1);
+    }
+
+    #[test]
+    fn test_collection_items_params() {
+        let params = CollectionItemsParams::new()
+            .sort(CollectionSort::Added)
+            .sort_order(SortOrder::Desc);
+        assert_eq!(
+            params.as_query_pairs(),
+            vec![
+                ("sort", "added".to_string()),
+                ("sort_order", "desc".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_instance_edit_validation() {
+        assert!(InstanceEdit::new().as_form_pairs().is_err());
+        assert!(InstanceEdit::new().rating(6).as_form_pairs().is_err());
+        assert!(InstanceEdit::new().folder_id(0).as_form_pairs().is_err());
+        assert_eq!(
+            InstanceEdit::new()
+                .rating(3)
+                .folder_id(4)
+                .as_form_pairs()
+                .unwrap(),
+            vec![("rating", "3".to_string()), ("folder_id", "4".to_string())]
+        );
+    }
+}
@@ -0,0 +1,633 @@
+use serde::{Deserialize, Deserializer};
+use std::time::Duration;
+
+use super::{Condition, ListingStatus};
+use crate::error::{DiscogsError, Result};
+
+/// The state of an inventory export or upload job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Pending,
+    Processing,
+    Success,
+    Failure,
+    /// A status not known to this crate; treated as still running.
+    #[serde(other)]
+    Other,
+}
+
+impl JobStatus {
+    /// Whether the job has stopped, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobStatus::Success | JobStatus::Failure)
+    }
+}
+
+/// An inventory export job.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InventoryExport {
+    pub id: u64,
+    pub status: JobStatus,
+    #[serde(default)]
+    pub created_ts: Option<String>,
+    #[serde(default)]
+    pub finished_ts: Option<String>,
+    #[serde(default)]
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// One listing in an inventory export CSV.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InventoryExportRow {
+    pub listing_id: u64,
+    pub release_id: u64,
+    #[serde(default)]
+    pub artist: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub catno: Option<String>,
+    #[serde(default)]
+    pub format: Option<String>,
+    pub status: ListingStatus,
+    /// Price in the seller's currency.
+    pub price: f64,
+    #[serde(default)]
+    pub listed: Option<String>,
+    #[serde(default)]
+    pub comments: Option<String>,
+    pub media_condition: Condition,
+    #[serde(default)]
+    pub sleeve_condition: Option<Condition>,
+    #[serde(default, deserialize_with = "yes_no")]
+    pub accept_offer: bool,
+    #[serde(default)]
+    pub external_id: Option<String>,
+    /// Weight in grams.
+    #[serde(default, deserialize_with = "crate::models::listing::auto_as_none")]
+    pub weight: Option<f64>,
+    #[serde(default, deserialize_with = "crate::models::listing::auto_as_none")]
+    pub format_quantity: Option<u32>,
+    #[serde(default)]
+    pub flat_shipping: Option<f64>,
+    #[serde(default)]
+    pub location: Option<String>,
+}
+
+impl InventoryExportRow {
+    /// Parse a downloaded inventory export.
+    pub fn from_csv(data: &[u8]) -> Result<Vec<InventoryExportRow>> {
+        let mut reader = csv::Reader::from_reader(data);
+        let rows = reader
+            .deserialize()
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(rows)
+    }
+}
+
+/// The kind of inventory upload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UploadType {
+    Add,
+    Change,
+    Delete,
+}
+
+/// An inventory upload job.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InventoryUpload {
+    pub id: u64,
+    pub status: JobStatus,
+    #[serde(default, rename = "type")]
+    pub upload_type: Option<UploadType>,
+    /// Summary of what the upload changed, once finished.
+    #[serde(default)]
+    pub results: Option<String>,
+    #[serde(default)]
+    pub created_ts: Option<String>,
+    #[serde(default)]
+    pub finished_ts: Option<String>,
+    #[serde(default)]
+    pub filename: Option<String>,
+}
+
+/// A new listing in an inventory "add" upload.
+#[derive(Debug, Clone)]
+pub struct UploadListing {
+    pub release_id: u64,
+    /// Price in the seller's currency.
+    pub price: f64,
+    pub media_condition: Condition,
+    pub sleeve_condition: Option<Condition>,
+    pub comments: Option<String>,
+    pub accept_offer: Option<bool>,
+    pub location: Option<String>,
+    pub external_id: Option<String>,
+    /// Weight in grams.
+    pub weight: Option<f64>,
+    pub format_quantity: Option<u32>,
+}
+
+impl UploadListing {
+    pub fn new(release_id: u64, media_condition: Condition, price: f64) -> Self {
+        Self {
+            release_id,
+            price,
+            media_condition,
+            sleeve_condition: None,
+            comments: None,
+            accept_offer: None,
+            location: None,
+            external_id: None,
+            weight: None,
+            format_quantity: None,
+        }
+    }
+
+    pub fn sleeve_condition(mut self, c: Condition) -> Self {
+        self.sleeve_condition = Some(c);
+        self
+    }
+
+    pub fn comments(mut self, c: impl Into<String>) -> Self {
+        self.comments = Some(c.into());
+        self
+    }
+
+    pub fn accept_offer(mut self, a: bool) -> Self {
+        self.accept_offer = Some(a);
+        self
+    }
+
+    pub fn location(mut self, l: impl Into<String>) -> Self {
+        self.location = Some(l.into());
+        self
+    }
+
+    pub fn external_id(mut self, e: impl Into<String>) -> Self {
+        self.external_id = Some(e.into());
+        self
+    }
+
+    pub fn weight(mut self, grams: f64) -> Self {
+        self.weight = Some(grams);
+        self
+    }
+
+    pub fn format_quantity(mut self, q: u32) -> Self {
+        self.format_quantity = Some(q);
+        self
+    }
+
+    /// Write listings as an "add" upload CSV.
+    pub fn to_csv(listings: &[UploadListing]) -> Result<Vec<u8>> {
+        for listing in listings {
+            if let Some(sleeve) = listing.sleeve_condition {
+                sleeve.known()?;
+            }
+            if !listing.media_condition.known()?.is_media_grade() {
+                return Err(DiscogsError::Configuration(format!(
+                    "\"{}\" is only valid as a sleeve condition",
+                    listing.media_condition
+                )));
+            }
+            if !(listing.price.is_finite() && listing.price > 0.0) {
+                return Err(DiscogsError::Configuration(format!(
+                    "price must be positive, got {}",
+                    listing.price
+                )));
+            }
+        }
+        write_upload_csv(listings.iter().map(|l| {
+            let mut fields = vec![
+                ("release_id", l.release_id.to_string()),
+                ("price", l.price.to_string()),
+                ("media_condition", l.media_condition.to_string()),
+            ];
+            OptionalFields {
+                sleeve_condition: l.sleeve_condition,
+                comments: l.comments.as_deref(),
+                accept_offer: l.accept_offer,
+                location: l.location.as_deref(),
+                external_id: l.external_id.as_deref(),
+                weight: l.weight,
+                format_quantity: l.format_quantity,
+            }
+            .push_to(&mut fields);
+            fields
+        }))
+    }
+}
+
+/// Changes to an existing listing in an inventory "change" upload.
+/// Unset fields are left as-is.
+#[derive(Debug, Clone)]
+pub struct ListingChange {
+    pub listing_id: u64,
+    pub release_id: Option<u64>,
+    pub price: Option<f64>,
+    pub media_condition: Option<Condition>,
+    pub sleeve_condition: Option<Condition>,
+    pub comments: Option<String>,
+    pub accept_offer: Option<bool>,
+    pub location: Option<String>,
+    pub external_id: Option<String>,
+    /// Weight in grams.
+    pub weight: Option<f64>,
+    pub format_quantity: Option<u32>,
+}
+
+impl ListingChange {
+    pub fn new(listing_id: u64) -> Self {
+        Self {
+            listing_id,
+            release_id: None,
+            price: None,
+            media_condition: None,
+            sleeve_condition: None,
+            comments: None,
+            accept_offer: None,
+            location: None,
+            external_id: None,
+            weight: None,
+            format_quantity: None,
+        }
+    }
+
+    pub fn release_id(mut self, r: u64) -> Self {
+        self.release_id = Some(r);
+        self
+    }
+
+    pub fn price(mut self, p: f64) -> Self {
+        self.price = Some(p);
+        self
+    }
+
+    pub fn media_condition(mut self, c: Condition) -> Self {
+        self.media_condition = Some(c);
+        self
+    }
+
+    pub fn sleeve_condition(mut self, c: Condition) -> Self {
+        self.sleeve_condition = Some(c);
+        self
+    }
+
+    pub fn comments(mut self, c: impl Into<String>) -> Self {
+        self.comments = Some(c.into());
+        self
+    }
+
+    pub fn accept_offer(mut self, a: bool) -> Self {
+        self.accept_offer = Some(a);
+        self
+    }
+
+    pub fn location(mut self, l: impl Into<String>) -> Self {
+        self.location = Some(l.into());
+        self
+    }
+
+    pub fn external_id(mut self, e: impl Into<String>) -> Self {
+        self.external_id = Some(e.into());
+        self
+    }
+
+    pub fn weight(mut self, grams: f64) -> Self {
+        self.weight = Some(grams);
+        self
+    }
+
+    pub fn format_quantity(mut self, q: u32) -> Self {
+        self.format_quantity = Some(q);
+        self
+    }
+
+    /// Write changes as "change" upload CSVs, one per distinct set of fields
+    /// changed. Each CSV only has the columns its rows set, so unset fields
+    /// are not cleared.
+    pub fn to_csv(changes: &[ListingChange]) -> Result<Vec<Vec<u8>>> {
+        for change in changes {
+            for condition in [change.media_condition, change.sleeve_condition]
+                .into_iter()
+                .flatten()
+            {
+                condition.known()?;
+            }
+            if let Some(price) = change.price {
+                if !(price.is_finite() && price > 0.0) {
+                    return Err(DiscogsError::Configuration(format!(
+                        "price must be positive, got {price}"
+                    )));
+                }
+            }
+        }
+        let rows = changes.iter().map(|c| {
+            let mut fields = vec![("listing_id", c.listing_id.to_string())];
+            if let Some(release_id) = c.release_id {
+                fields.push(("release_id", release_id.to_string()));
+            }
+            if let Some(price) = c.price {
+                fields.push(("price", price.to_string()));
+            }
+            if let Some(condition) = c.media_condition {
+                fields.push(("media_condition", condition.to_string()));
+            }
+            OptionalFields {
+                sleeve_condition: c.sleeve_condition,
+                comments: c.comments.as_deref(),
+                accept_offer: c.accept_offer,
+                location: c.location.as_deref(),
+                external_id: c.external_id.as_deref(),
+                weight: c.weight,
+                format_quantity: c.format_quantity,
+            }
+            .push_to(&mut fields);
+            fields
+        });
+
+        // Rows setting the same fields share a CSV.
+        let mut groups: Vec<(Vec<&str>, Vec<_>)> = Vec::new();
+        for row in rows {
+            let columns: Vec<&str> = row.iter().map(|(column, _)| *column).collect();
+            match groups.iter_mut().find(|(c, _)| *c == columns) {
+                Some((_, group)) => group.push(row),
+                None => groups.push((columns, vec![row])),
+            }
+        }
+        if groups.is_empty() {
+            return Err(DiscogsError::Configuration(
+                "inventory upload has no rows".into(),
+            ));
+        }
+        groups
+            .into_iter()
+            .map(|(_, group)| write_upload_csv(group.into_iter()))
+            .collect()
+    }
+}
+
+/// Write listing IDs as a "delete" upload CSV.
+pub(crate) fn delete_csv(listing_ids: &[u64]) -> Result<Vec<u8>> {
+    write_upload_csv(
+        listing_ids
+            .iter()
+            .map(|id| vec![("listing_id", id.to_string())]),
+    )
+}
+
+/// Column order of inventory upload CSVs.
+const UPLOAD_COLUMNS: &[&str] = &[
+    "listing_id",
+    "release_id",
+    "price",
+    "media_condition",
+    "sleeve_condition",
+    "comments",
+    "accept_offer",
+    "location",
+    "external_id",
+    "weight",
+    "format_quantity",
+];
+
+/// The upload columns shared by "add" and "change" rows.
+struct OptionalFields<'a> {
+    sleeve_condition: Option<Condition>,
+    comments: Option<&'a str>,
+    accept_offer: Option<bool>,
+    location: Option<&'a str>,
+    external_id: Option<&'a str>,
+    weight: Option<f64>,
+    format_quantity: Option<u32>,
+}
+
+impl OptionalFields<'_> {
+    fn push_to(self, fields: &mut Vec<(&'static str, String)>) {
+        if let Some(condition) = self.sleeve_condition {
+            fields.push(("sleeve_condition", condition.to_string()));
+        }
+        if let Some(comments) = self.comments {
+            fields.push(("comments", comments.to_string()));
+        }
+        if let Some(accept) = self.accept_offer {
+            fields.push(("accept_offer", if accept { "Y" } else { "N" }.to_string()));
+        }
+        if let Some(location) = self.location {
+            fields.push(("location", location.to_string()));
+        }
+        if let Some(external_id) = self.external_id {
+            fields.push(("external_id", external_id.to_string()));
+        }
+        if let Some(weight) = self.weight {
+            fields.push(("weight", weight.to_string()));
+        }
+        if let Some(quantity) = self.format_quantity {
+            fields.push(("format_quantity", quantity.to_string()));
+        }
+    }
+}
+
+/// Write rows of `(column, value)` pairs as CSV, with a header of every
+/// column used by any row.
+fn write_upload_csv<I>(rows: I) -> Result<Vec<u8>>
+where
+    I: Iterator<Item = Vec<(&'static str, String)>>,
+{
+    let rows: Vec<_> = rows.collect();
+    if rows.is_empty() {
+        return Err(DiscogsError::Configuration(
+            "inventory upload has no rows".into(),
+        ));
+    }
+    let columns: Vec<&str> = UPLOAD_COLUMNS
+        .iter()
+        .copied()
+        .filter(|column| rows.iter().any(|row| row.iter().any(|(c, _)| c == column)))
+        .collect();
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&columns)?;
+    for row in &rows {
+        writer.write_record(columns.iter().map(|column| {
+            row.iter()
+                .find(|(c, _)| c == column)
+                .map_or("", |(_, value)| value.as_str())
+        }))?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| DiscogsError::Io(e.into_error()))
+}
+
+/// Deserialize the `Y`/`N` flags used in inventory CSVs.
+fn yes_no<'de, D>(deserializer: D) -> std::result::Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes" | "true" | "1"
+    ))
+}
+
+/// How to wait for an inventory job to finish.
+///
+/// The delay between status checks starts at `initial_interval` and doubles
+/// up to `max_interval`. Waiting gives up after `timeout`; `Duration::MAX`
+/// waits indefinitely.
+#[derive(Debug, Clone)]
+pub struct PollOptions {
+    pub initial_interval: Duration,
+    pub max_interval: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_secs(2),
+            max_interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+impl PollOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn initial_interval(mut self, interval: Duration) -> Self {
+        self.initial_interval = interval;
+        self
+    }
+
+    pub fn max_interval(mut self, interval: Duration) -> Self {
+        self.max_interval = interval;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_inventory_export() {
+        let json = r#"{
+            "status": "success",
+            "created_ts": "2018-09-27T12:59:02",
+            "url": "https://api.discogs.com/inventory/export/599632",
+            "finished_ts": "2018-09-27T12:59:02",
+            "download_url": "https://api.discogs.com/inventory/export/599632/download",
+            "filename": "cburmeister-inventory-20180927-1259.csv",
+            "id": 599632
+        }"#;
+        let export: InventoryExport = serde_json::from_str(json).unwrap();
+        assert_eq!(export.id, 599632);
+        assert!(export.status.is_finished());
+
+        let pending: JobStatus = serde_json::from_str(r#""in progress""#).unwrap();
+        assert_eq!(pending, JobStatus::Other);
+        assert!(!pending.is_finished());
+    }
+
+    #[test]
+    fn test_parse_inventory_export_csv() {
+        let csv = "\
+listing_id,artist,title,label,catno,format,release_id,status,price,listed,comments,media_condition,sleeve_condition,accept_offer,external_id,weight,format_quantity,flat_shipping,location
+123,Rick Astley,Never Gonna Give You Up,RCA,PB 49801,\"Vinyl, 7\"\"\",249504,For Sale,12.50,2024-01-02 10:00:00,\"Plays fine, light scuffs\",Very Good Plus (VG+),Generic,Y,sku-1,60,auto,,Shelf A
+124,The Persuader,Stockholm,Svek,SK032,\"Vinyl, 12\"\"\",1,Draft,30.00,,,Near Mint (NM or M-),,N,,,2,,
+";
+        let rows = InventoryExportRow::from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].media_condition, Condition::VeryGoodPlus);
+        assert_eq!(rows[0].sleeve_condition, Some(Condition::Generic));
+        assert_eq!(rows[0].format.as_deref(), Some("Vinyl, 7\""));
+        assert!(rows[0].accept_offer);
+        assert_eq!(rows[0].weight, Some(60.0));
+        assert_eq!(rows[0].format_quantity, None);
+        assert_eq!(rows[1].status, ListingStatus::Draft);
+        assert_eq!(rows[1].sleeve_condition, None);
+        assert_eq!(rows[1].comments, None);
+        assert_eq!(rows[1].format_quantity, Some(2));
+        assert!(!rows[1].accept_offer);
+    }
+
+    #[test]
+    fn test_upload_listing_csv() {
+        let listings = [
+            UploadListing::new(249504, Condition::VeryGoodPlus, 12.5)
+                .comments("Plays fine, light scuffs")
+                .accept_offer(true),
+            UploadListing::new(1, Condition::Mint, 30.0).sleeve_condition(Condition::NearMint),
+        ];
+        let csv = String::from_utf8(UploadListing::to_csv(&listings).unwrap()).unwrap();
+        assert_eq!(
+            csv,
+            "release_id,price,media_condition,sleeve_condition,comments,accept_offer\n\
+             249504,12.5,Very Good Plus (VG+),,\"Plays fine, light scuffs\",Y\n\
+             1,30,Mint (M),Near Mint (NM or M-),,\n"
+        );
+
+        let invalid = [UploadListing::new(1, Condition::Generic, 5.0)];
+        assert!(UploadListing::to_csv(&invalid).is_err());
+        let unknown = [UploadListing::new(1, Condition::Other, 5.0)];
+        assert!(UploadListing::to_csv(&unknown).is_err());
+        let unknown = [ListingChange::new(123).sleeve_condition(Condition::Other)];
+        assert!(ListingChange::to_csv(&unknown).is_err());
+        for price in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let invalid = [ListingChange::new(123).price(price)];
+            assert!(matches!(
+                ListingChange::to_csv(&invalid),
+                Err(DiscogsError::Configuration(_))
+            ));
+        }
+        assert!(UploadListing::to_csv(&[]).is_err());
+    }
+
+    #[test]
+    fn test_listing_change_csv_only_includes_set_columns() {
+        let changes = [
+            ListingChange::new(123).price(15.0),
+            ListingChange::new(124).location("Shelf B"),
+            ListingChange::new(125).price(20.0),
+        ];
+        let csvs: Vec<String> = ListingChange::to_csv(&changes)
+            .unwrap()
+            .into_iter()
+            .map(|csv| String::from_utf8(csv).unwrap())
+            .collect();
+        assert_eq!(
+            csvs,
+            vec![
+                "listing_id,price\n123,15\n125,20\n",
+                "listing_id,location\n124,Shelf B\n"
+            ]
+        );
+        // No row has an empty cell for a column it didn't set.
+        for csv in &csvs {
+            assert!(!csv.contains(",,") && !csv.contains(",\n"));
+        }
+        assert!(ListingChange::to_csv(&[]).is_err());
+
+        let csv = String::from_utf8(delete_csv(&[123, 124]).unwrap()).unwrap();
+        assert_eq!(csv, "listing_id\n123\n124\n");
+    }
+}
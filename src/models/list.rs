@@ -0,0 +1,137 @@
+use serde::Deserialize;
+
+use super::UserSummary;
+
+/// A list as it appears in a user's lists.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListSummary {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub public: bool,
+    #[serde(default)]
+    pub date_added: Option<String>,
+    #[serde(default)]
+    pub date_changed: Option<String>,
+    #[serde(default)]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub uri: Option<String>,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+}
+
+/// A user-curated list of releases, masters, artists and labels.
+#[derive(Debug, Clone, Deserialize)]
+pub struct List {
+    #[serde(rename = "list_id")]
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub public: bool,
+    #[serde(default)]
+    pub created_ts: Option<String>,
+    #[serde(default)]
+    pub modified_ts: Option<String>,
+    #[serde(default)]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+    #[serde(default)]
+    pub user: Option<UserSummary>,
+    #[serde(default)]
+    pub items: Vec<ListItem>,
+}
+
+/// An entry in a list, with the curator's comment.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListItem {
+    /// What the entry refers to.
+    #[serde(flatten)]
+    pub entity: ListEntity,
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub display_title: Option<String>,
+    #[serde(default)]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub uri: Option<String>,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+}
+
+/// The database entity a list item refers to. Resolve it with
+/// [`DiscogsClient::release`](crate::DiscogsClient::release),
+/// [`master`](crate::DiscogsClient::master),
+/// [`artist`](crate::DiscogsClient::artist) or
+/// [`label`](crate::DiscogsClient::label).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ListEntity {
+    Release {
+        id: u64,
+    },
+    Master {
+        id: u64,
+    },
+    Artist {
+        id: u64,
+    },
+    Label {
+        id: u64,
+    },
+    /// An entity type not known to this crate.
+    #[serde(other)]
+    Other,
+}
+
+impl ListEntity {
+    /// The ID of the referenced entity, if its type is known.
+    pub fn id(&self) -> Option<u64> {
+        match self {
+            ListEntity::Release { id }
+            | ListEntity::Master { id }
+            | ListEntity::Artist { id }
+            | ListEntity::Label { id } => Some(*id),
+            ListEntity::Other => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_list() {
+        let json = r#"{
+            "list_id": 21,
+            "name": "Best Of Discogs",
+            "public": true,
+            "user": {"id": 1, "username": "memory"},
+            "items": [
+                {"type": "release", "id": 249504, "comment": "A classic",
+                 "display_title": "Rick Astley - Never Gonna Give You Up"},
+                {"type": "master", "id": 96559},
+                {"type": "artist", "id": 72872},
+                {"type": "label", "id": 895},
+                {"type": "track", "id": 7}
+            ]
+        }"#;
+        let list: List = serde_json::from_str(json).unwrap();
+        assert_eq!(list.id, 21);
+        assert_eq!(list.user.unwrap().username, "memory");
+        assert_eq!(list.items[0].entity, ListEntity::Release { id: 249504 });
+        assert_eq!(list.items[0].comment.as_deref(), Some("A classic"));
+        assert_eq!(list.items[1].entity, ListEntity::Master { id: 96559 });
+        assert_eq!(list.items[3].entity.id(), Some(895));
+        assert_eq!(list.items[4].entity, ListEntity::Other);
+    }
+}
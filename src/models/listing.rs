@@ -0,0 +1,561 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+use super::{Money, SortOrder};
+use crate::error::{DiscogsError, Result};
+
+/// Media or sleeve grading on the Goldmine scale used by the marketplace.
+///
+/// `Generic`, `NotGraded` and `NoCover` only apply to sleeves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Condition {
+    #[serde(rename = "Mint (M)")]
+    Mint,
+    #[serde(rename = "Near Mint (NM or M-)")]
+    NearMint,
+    #[serde(rename = "Very Good Plus (VG+)")]
+    VeryGoodPlus,
+    #[serde(rename = "Very Good (VG)")]
+    VeryGood,
+    #[serde(rename = "Good Plus (G+)")]
+    GoodPlus,
+    #[serde(rename = "Good (G)")]
+    Good,
+    #[serde(rename = "Fair (F)")]
+    Fair,
+    #[serde(rename = "Poor (P)")]
+    Poor,
+    #[serde(rename = "Generic")]
+    Generic,
+    #[serde(rename = "Not Graded")]
+    NotGraded,
+    #[serde(rename = "No Cover")]
+    NoCover,
+    /// A grade not known to this crate. Listings can't be given it.
+    #[serde(other)]
+    Other,
+}
+
+impl Condition {
+    /// The label the API uses for this grade, e.g. `"Very Good Plus (VG+)"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Condition::Mint => "Mint (M)",
+            Condition::NearMint => "Near Mint (NM or M-)",
+            Condition::VeryGoodPlus => "Very Good Plus (VG+)",
+            Condition::VeryGood => "Very Good (VG)",
+            Condition::GoodPlus => "Good Plus (G+)",
+            Condition::Good => "Good (G)",
+            Condition::Fair => "Fair (F)",
+            Condition::Poor => "Poor (P)",
+            Condition::Generic => "Generic",
+            Condition::NotGraded => "Not Graded",
+            Condition::NoCover => "No Cover",
+            Condition::Other => "Other",
+        }
+    }
+
+    /// Return the grade, or an error for [`Condition::Other`], which has no
+    /// label the API would accept.
+    pub(crate) fn known(self) -> Result<Self> {
+        if self == Condition::Other {
+            return Err(DiscogsError::Configuration(
+                "listings can't be given an unknown condition".into(),
+            ));
+        }
+        Ok(self)
+    }
+
+    /// Whether this grade can describe the media itself (as opposed to only a sleeve).
+    pub fn is_media_grade(&self) -> bool {
+        !matches!(
+            self,
+            Condition::Generic | Condition::NotGraded | Condition::NoCover
+        )
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The status of a marketplace listing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ListingStatus {
+    #[serde(rename = "For Sale")]
+    ForSale,
+    #[serde(rename = "Draft")]
+    Draft,
+    #[serde(rename = "Expired")]
+    Expired,
+    #[serde(rename = "Sold")]
+    Sold,
+    #[serde(rename = "Deleted")]
+    Deleted,
+    #[serde(rename = "Suspended")]
+    Suspended,
+    #[serde(rename = "Violation")]
+    Violation,
+    /// A status not known to this crate.
+    #[serde(other)]
+    Other,
+}
+
+impl ListingStatus {
+    /// The label the API uses for this status, e.g. `"For Sale"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListingStatus::ForSale => "For Sale",
+            ListingStatus::Draft => "Draft",
+            ListingStatus::Expired => "Expired",
+            ListingStatus::Sold => "Sold",
+            ListingStatus::Deleted => "Deleted",
+            ListingStatus::Suspended => "Suspended",
+            ListingStatus::Violation => "Violation",
+            ListingStatus::Other => "Other",
+        }
+    }
+}
+
+impl fmt::Display for ListingStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Fields for creating or editing a marketplace listing.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListingParams {
+    pub release_id: u64,
+    pub condition: Condition,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sleeve_condition: Option<Condition>,
+    /// Price in the seller's currency.
+    pub price: f64,
+    pub status: ListingStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comments: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_offers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Weight in grams. Discogs estimates it from the format when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    /// Number of items counted for shipping. Discogs derives it from the format when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format_quantity: Option<u32>,
+}
+
+impl ListingParams {
+    /// A new listing that goes on sale immediately.
+    pub fn new(release_id: u64, condition: Condition, price: f64) -> Self {
+        Self {
+            release_id,
+            condition,
+            sleeve_condition: None,
+            price,
+            status: ListingStatus::ForSale,
+            comments: None,
+            allow_offers: None,
+            external_id: None,
+            location: None,
+            weight: None,
+            format_quantity: None,
+        }
+    }
+
+    pub fn sleeve_condition(mut self, c: Condition) -> Self {
+        self.sleeve_condition = Some(c);
+        self
+    }
+
+    pub fn status(mut self, s: ListingStatus) -> Self {
+        self.status = s;
+        self
+    }
+
+    pub fn comments(mut self, c: impl Into<String>) -> Self {
+        self.comments = Some(c.into());
+        self
+    }
+
+    pub fn allow_offers(mut self, a: bool) -> Self {
+        self.allow_offers = Some(a);
+        self
+    }
+
+    pub fn external_id(mut self, e: impl Into<String>) -> Self {
+        self.external_id = Some(e.into());
+        self
+    }
+
+    pub fn location(mut self, l: impl Into<String>) -> Self {
+        self.location = Some(l.into());
+        self
+    }
+
+    pub fn weight(mut self, grams: f64) -> Self {
+        self.weight = Some(grams);
+        self
+    }
+
+    pub fn format_quantity(mut self, q: u32) -> Self {
+        self.format_quantity = Some(q);
+        self
+    }
+
+    /// Check the fields the API would otherwise reject with a 422.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !self.condition.known()?.is_media_grade() {
+            return Err(DiscogsError::Configuration(format!(
+                "\"{}\" is only valid as a sleeve condition",
+                self.condition
+            )));
+        }
+        if let Some(sleeve) = self.sleeve_condition {
+            sleeve.known()?;
+        }
+        if !(self.price.is_finite() && self.price > 0.0) {
+            return Err(DiscogsError::Configuration(format!(
+                "price must be positive, got {}",
+                self.price
+            )));
+        }
+        if !matches!(self.status, ListingStatus::ForSale | ListingStatus::Draft) {
+            return Err(DiscogsError::Configuration(format!(
+                "listing status must be \"For Sale\" or \"Draft\", got \"{}\"",
+                self.status
+            )));
+        }
+        if let Some(weight) = self.weight {
+            if !(weight.is_finite() && weight > 0.0) {
+                return Err(DiscogsError::Configuration(format!(
+                    "weight must be positive, got {weight}"
+                )));
+            }
+        }
+        if self.format_quantity == Some(0) {
+            return Err(DiscogsError::Configuration(
+                "format quantity must be at least 1".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The listing created by [`DiscogsClient::create_listing`](crate::DiscogsClient::create_listing).
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreatedListing {
+    pub listing_id: u64,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+}
+
+/// A marketplace listing.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Listing {
+    pub id: u64,
+    pub status: ListingStatus,
+    pub price: Money,
+    #[serde(default)]
+    pub original_price: Option<Money>,
+    #[serde(default)]
+    pub shipping_price: Option<Money>,
+    pub condition: Condition,
+    #[serde(default)]
+    pub sleeve_condition: Option<Condition>,
+    #[serde(default)]
+    pub allow_offers: bool,
+    #[serde(default)]
+    pub comments: Option<String>,
+    #[serde(default)]
+    pub ships_from: Option<String>,
+    #[serde(default)]
+    pub posted: Option<String>,
+    #[serde(default)]
+    pub audio: bool,
+    /// Private to the seller.
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Private to the seller.
+    #[serde(default)]
+    pub external_id: Option<String>,
+    /// Weight in grams. Private to the seller.
+    #[serde(default, deserialize_with = "auto_as_none")]
+    pub weight: Option<f64>,
+    /// Private to the seller.
+    #[serde(default, deserialize_with = "auto_as_none")]
+    pub format_quantity: Option<u32>,
+    #[serde(default)]
+    pub uri: Option<String>,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+    pub seller: Seller,
+    pub release: ListingRelease,
+}
+
+/// Deserialize a number that the API may report as `"auto"` instead.
+pub(crate) fn auto_as_none<'de, D, T>(deserializer: D) -> std::result::Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrAuto<T> {
+        Number(T),
+        Auto(#[allow(dead_code)] String),
+    }
+
+    Ok(
+        match Option::<NumberOrAuto<T>>::deserialize(deserializer)? {
+            Some(NumberOrAuto::Number(n)) => Some(n),
+            _ => None,
+        },
+    )
+}
+
+/// The seller of a listing.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Seller {
+    pub id: u64,
+    pub username: String,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub shipping: Option<String>,
+    #[serde(default)]
+    pub payment: Option<String>,
+    #[serde(default)]
+    pub stats: Option<SellerStats>,
+}
+
+/// Seller feedback statistics.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SellerStats {
+    /// Percentage of positive feedback, e.g. `"99.8"`.
+    #[serde(default)]
+    pub rating: Option<String>,
+    #[serde(default)]
+    pub stars: Option<f64>,
+    #[serde(default)]
+    pub total: Option<u32>,
+}
+
+/// Summary of the release embedded in a listing.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListingRelease {
+    pub id: u64,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub artist: Option<String>,
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub year: Option<u32>,
+    #[serde(default)]
+    pub catalog_number: Option<String>,
+    #[serde(default)]
+    pub thumbnail: Option<String>,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+}
+
+/// Field to sort a seller's inventory by.
+#[derive(Debug, Clone)]
+pub enum InventorySort {
+    Listed,
+    Price,
+    Item,
+    Artist,
+    Label,
+    Catno,
+    Audio,
+    Status,
+    Location,
+}
+
+impl fmt::Display for InventorySort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InventorySort::Listed => write!(f, "listed"),
+            InventorySort::Price => write!(f, "price"),
+            InventorySort::Item => write!(f, "item"),
+            InventorySort::Artist => write!(f, "artist"),
+            InventorySort::Label => write!(f, "label"),
+            InventorySort::Catno => write!(f, "catno"),
+            InventorySort::Audio => write!(f, "audio"),
+            InventorySort::Status => write!(f, "status"),
+            InventorySort::Location => write!(f, "location"),
+        }
+    }
+}
+
+/// Filters for browsing a seller's inventory.
+///
+/// Statuses other than "For Sale" are only visible to the seller.
+#[derive(Debug, Clone, Default)]
+pub struct InventoryParams {
+    pub status: Option<ListingStatus>,
+    pub sort: Option<InventorySort>,
+    pub sort_order: Option<SortOrder>,
+}
+
+impl InventoryParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, s: ListingStatus) -> Self {
+        self.status = Some(s);
+        self
+    }
+
+    pub fn sort(mut self, s: InventorySort) -> Self {
+        self.sort = Some(s);
+        self
+    }
+
+    pub fn sort_order(mut self, o: SortOrder) -> Self {
+        self.sort_order = Some(o);
+        self
+    }
+
+    pub(crate) fn as_query_pairs(&self) -> Vec<(&str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(ref s) = self.status {
+            pairs.push(("status", s.to_string()));
+        }
+        if let Some(ref s) = self.sort {
+            pairs.push(("sort", s.to_string()));
+        }
+        if let Some(ref o) = self.sort_order {
+            pairs.push(("sort_order", o.to_string()));
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_condition_round_trip() {
+        let json = serde_json::to_string(&Condition::VeryGoodPlus).unwrap();
+        assert_eq!(json, r#""Very Good Plus (VG+)""#);
+        let parsed: Condition = serde_json::from_str(r#""No Cover""#).unwrap();
+        assert_eq!(parsed, Condition::NoCover);
+    }
+
+    #[test]
+    fn test_listing_params_serialize() {
+        let params = ListingParams::new(1, Condition::Mint, 10.0)
+            .sleeve_condition(Condition::Generic)
+            .status(ListingStatus::Draft);
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "release_id": 1,
+                "condition": "Mint (M)",
+                "sleeve_condition": "Generic",
+                "price": 10.0,
+                "status": "Draft"
+            })
+        );
+    }
+
+    #[test]
+    fn test_listing_params_validation() {
+        assert!(ListingParams::new(1, Condition::Mint, 10.0)
+            .validate()
+            .is_ok());
+        assert!(ListingParams::new(1, Condition::NoCover, 10.0)
+            .validate()
+            .is_err());
+        assert!(ListingParams::new(1, Condition::Mint, 0.0)
+            .validate()
+            .is_err());
+        assert!(ListingParams::new(1, Condition::Mint, 10.0)
+            .status(ListingStatus::Sold)
+            .validate()
+            .is_err());
+        assert!(ListingParams::new(1, Condition::Mint, 10.0)
+            .format_quantity(0)
+            .validate()
+            .is_err());
+        assert!(ListingParams::new(1, Condition::Other, 10.0)
+            .validate()
+            .is_err());
+        assert!(ListingParams::new(1, Condition::Mint, 10.0)
+            .sleeve_condition(Condition::Other)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_unknown_grade_and_status_deserialize_as_other() {
+        let condition: Condition = serde_json::from_str(r#""Very Good Minus (VG-)""#).unwrap();
+        assert_eq!(condition, Condition::Other);
+        let status: ListingStatus = serde_json::from_str(r#""On Hold""#).unwrap();
+        assert_eq!(status, ListingStatus::Other);
+    }
+
+    #[test]
+    fn test_deserialize_listing() {
+        let json = r#"{
+            "id": 150899904,
+            "status": "For Sale",
+            "price": {"currency": "USD", "value": 149.99},
+            "original_price": {"curr_abbr": "USD", "value": 149.99, "formatted": "$149.99"},
+            "shipping_price": {"currency": "USD", "value": 5.0},
+            "condition": "Near Mint (NM or M-)",
+            "sleeve_condition": "Generic",
+            "allow_offers": true,
+            "format_quantity": "auto",
+            "weight": 230.0,
+            "seller": {"id": 1580364, "username": "rappcats", "stats": {"rating": "100.0", "stars": 5.0, "total": 12}},
+            "release": {"id": 5610049, "description": "LAMB - Fear Of Fours (LP, Album)", "catalog_number": "BM-10"}
+        }"#;
+        let listing: Listing = serde_json::from_str(json).unwrap();
+        assert_eq!(listing.status, ListingStatus::ForSale);
+        assert_eq!(
+            listing.price,
+            Money::new(149.99, crate::models::Currency::Usd)
+        );
+        assert_eq!(listing.sleeve_condition, Some(Condition::Generic));
+        assert_eq!(listing.format_quantity, None);
+        assert_eq!(listing.weight, Some(230.0));
+        assert_eq!(listing.seller.username, "rappcats");
+        assert_eq!(listing.release.catalog_number.as_deref(), Some("BM-10"));
+    }
+
+    #[test]
+    fn test_inventory_params() {
+        let params = InventoryParams::new()
+            .status(ListingStatus::ForSale)
+            .sort(InventorySort::Price)
+            .sort_order(SortOrder::Asc);
+        assert_eq!(
+            params.as_query_pairs(),
+            vec![
+                ("status", "For Sale".to_string()),
+                ("sort", "price".to_string()),
+                ("sort_order", "asc".to_string())
+            ]
+        );
+    }
+}
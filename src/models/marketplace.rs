@@ -0,0 +1,643 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::{Condition, Currency, Money, SortOrder};
+use crate::error::{DiscogsError, Result};
+
+/// The status of a marketplace order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OrderStatus {
+    #[serde(rename = "New Order")]
+    NewOrder,
+    #[serde(rename = "Buyer Contacted")]
+    BuyerContacted,
+    #[serde(rename = "Invoice Sent")]
+    InvoiceSent,
+    #[serde(rename = "Payment Pending")]
+    PaymentPending,
+    #[serde(rename = "Payment Received")]
+    PaymentReceived,
+    #[serde(rename = "In Progress")]
+    InProgress,
+    #[serde(rename = "Shipped")]
+    Shipped,
+    #[serde(rename = "Refund Sent")]
+    RefundSent,
+    #[serde(rename = "Merged")]
+    Merged,
+    #[serde(rename = "Order Changed")]
+    OrderChanged,
+    #[serde(rename = "Cancelled (Non-Paying Buyer)")]
+    CancelledNonPayingBuyer,
+    #[serde(rename = "Cancelled (Item Unavailable)")]
+    CancelledItemUnavailable,
+    #[serde(rename = "Cancelled (Per Buyer's Request)")]
+    CancelledPerBuyersRequest,
+    #[serde(rename = "Cancelled (Refund Received)")]
+    CancelledRefundReceived,
+    /// A status not known to this crate. Orders can't be moved to it.
+    #[serde(other)]
+    Other,
+}
+
+impl OrderStatus {
+    /// The label the API uses for this status, e.g. `"Payment Received"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::NewOrder => "New Order",
+            OrderStatus::BuyerContacted => "Buyer Contacted",
+            OrderStatus::InvoiceSent => "Invoice Sent",
+            OrderStatus::PaymentPending => "Payment Pending",
+            OrderStatus::PaymentReceived => "Payment Received",
+            OrderStatus::InProgress => "In Progress",
+            OrderStatus::Shipped => "Shipped",
+            OrderStatus::RefundSent => "Refund Sent",
+            OrderStatus::Merged => "Merged",
+            OrderStatus::OrderChanged => "Order Changed",
+            OrderStatus::CancelledNonPayingBuyer => "Cancelled (Non-Paying Buyer)",
+            OrderStatus::CancelledItemUnavailable => "Cancelled (Item Unavailable)",
+            OrderStatus::CancelledPerBuyersRequest => "Cancelled (Per Buyer's Request)",
+            OrderStatus::CancelledRefundReceived => "Cancelled (Refund Received)",
+            OrderStatus::Other => "Other",
+        }
+    }
+
+    /// Whether the order is closed and can no longer change status.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Merged
+                | OrderStatus::CancelledNonPayingBuyer
+                | OrderStatus::CancelledItemUnavailable
+                | OrderStatus::CancelledPerBuyersRequest
+                | OrderStatus::CancelledRefundReceived
+        )
+    }
+
+    /// Whether the buyer has paid for the order.
+    pub fn is_paid(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::PaymentReceived
+                | OrderStatus::InProgress
+                | OrderStatus::Shipped
+                | OrderStatus::RefundSent
+        )
+    }
+
+    /// Whether a seller may move an order from this status to `next`.
+    ///
+    /// `New Order`, `Merged` and `Order Changed` are only ever set by
+    /// Discogs, closed orders stay closed, and refunds require payment.
+    /// Nothing is allowed to or from [`OrderStatus::Other`], since its rules
+    /// aren't known; Discogs' `next_status` list is the only guide there.
+    pub fn can_transition_to(&self, next: OrderStatus) -> bool {
+        if self.is_terminal() || *self == OrderStatus::Other {
+            return false;
+        }
+        match next {
+            OrderStatus::NewOrder
+            | OrderStatus::Merged
+            | OrderStatus::OrderChanged
+            | OrderStatus::Other => false,
+            OrderStatus::RefundSent | OrderStatus::CancelledRefundReceived => self.is_paid(),
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A marketplace order.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Order {
+    /// Order IDs look like `"1234-56"`.
+    pub id: String,
+    pub status: OrderStatus,
+    /// Statuses the seller may move the order to, as reported by Discogs.
+    #[serde(default)]
+    pub next_status: Vec<OrderStatus>,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub created: Option<String>,
+    #[serde(default)]
+    pub last_activity: Option<String>,
+    #[serde(default)]
+    pub items: Vec<OrderItem>,
+    #[serde(default)]
+    pub total: Option<Money>,
+    #[serde(default)]
+    pub fee: Option<Money>,
+    #[serde(default)]
+    pub shipping: Option<OrderShipping>,
+    #[serde(default)]
+    pub shipping_address: Option<String>,
+    #[serde(default)]
+    pub additional_instructions: Option<String>,
+    pub seller: UserSummary,
+    pub buyer: UserSummary,
+    #[serde(default)]
+    pub uri: Option<String>,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+    #[serde(default)]
+    pub messages_url: Option<String>,
+}
+
+/// A listing sold as part of an order.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderItem {
+    /// The listing ID.
+    pub id: u64,
+    pub release: OrderRelease,
+    pub price: Money,
+    #[serde(default)]
+    pub media_condition: Option<Condition>,
+    #[serde(default)]
+    pub sleeve_condition: Option<Condition>,
+}
+
+/// Summary of the release embedded in an order item.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderRelease {
+    pub id: u64,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Shipping charged on an order.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "RawOrderShipping")]
+pub struct OrderShipping {
+    pub method: Option<String>,
+    pub value: Money,
+}
+
+/// Shipping as the API sends it, with the currency beside the value and
+/// sometimes missing.
+#[derive(Deserialize)]
+struct RawOrderShipping {
+    #[serde(default)]
+    method: Option<String>,
+    value: f64,
+    #[serde(default)]
+    currency: Option<Currency>,
+}
+
+impl From<RawOrderShipping> for OrderShipping {
+    fn from(raw: RawOrderShipping) -> Self {
+        Self {
+            method: raw.method,
+            value: Money {
+                amount: raw.value,
+                currency: raw.currency,
+            },
+        }
+    }
+}
+
+/// A user referenced by an order or list.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserSummary {
+    pub id: u64,
+    pub username: String,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+}
+
+/// A reference to an order from another resource.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderRef {
+    pub id: String,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+}
+
+/// The kind of entry in an order's message thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageType {
+    /// A message written by the buyer or seller.
+    Message,
+    /// The order status changed.
+    Status,
+    /// The shipping cost was set or changed.
+    Shipping,
+    RefundSent,
+    RefundReceived,
+    /// A type not known to this crate.
+    #[serde(other)]
+    Other,
+}
+
+/// An entry in an order's message thread.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderMessage {
+    #[serde(rename = "type")]
+    pub message_type: MessageType,
+    #[serde(default)]
+    pub subject: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Absent for entries generated by Discogs.
+    #[serde(default)]
+    pub from: Option<UserSummary>,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    #[serde(default)]
+    pub order: Option<OrderRef>,
+    /// Present on `refund_sent` and `refund_received` entries.
+    #[serde(default)]
+    pub refund: Option<Refund>,
+}
+
+/// Refund details attached to an order message.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Refund {
+    /// Amount refunded. Discogs usually sends a bare number, in the order's
+    /// currency, leaving `currency` as `None`.
+    pub amount: Money,
+    #[serde(default)]
+    pub order: Option<OrderRef>,
+}
+
+/// Marketplace statistics for a release.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MarketplaceStats {
+    /// `None` when nothing is for sale.
+    #[serde(default)]
+    pub lowest_price: Option<Money>,
+    #[serde(default)]
+    pub num_for_sale: Option<u32>,
+    /// Whether the release is blocked from sale on the marketplace.
+    #[serde(default)]
+    pub blocked_from_sale: bool,
+}
+
+/// Field to sort orders by.
+#[derive(Debug, Clone)]
+pub enum OrderSort {
+    Id,
+    Buyer,
+    Created,
+    Status,
+    LastActivity,
+}
+
+impl fmt::Display for OrderSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderSort::Id => write!(f, "id"),
+            OrderSort::Buyer => write!(f, "buyer"),
+            OrderSort::Created => write!(f, "created"),
+            OrderSort::Status => write!(f, "status"),
+            OrderSort::LastActivity => write!(f, "last_activity"),
+        }
+    }
+}
+
+/// Filters for listing the authenticated seller's orders.
+#[derive(Debug, Clone, Default)]
+pub struct OrderParams {
+    pub status: Option<OrderStatus>,
+    /// ISO 8601 timestamp, e.g. `"2019-06-24T20:58:58Z"`.
+    pub created_after: Option<String>,
+    /// ISO 8601 timestamp, e.g. `"2019-06-24T20:58:58Z"`.
+    pub created_before: Option<String>,
+    pub archived: Option<bool>,
+    pub sort: Option<OrderSort>,
+    pub sort_order: Option<SortOrder>,
+}
+
+impl OrderParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, s: OrderStatus) -> Self {
+        self.status = Some(s);
+        self
+    }
+
+    pub fn created_after(mut self, t: impl Into<String>) -> Self {
+        self.created_after = Some(t.into());
+        self
+    }
+
+    pub fn created_before(mut self, t: impl Into<String>) -> Self {
+        self.created_before = Some(t.into());
+        self
+    }
+
+    pub fn archived(mut self, a: bool) -> Self {
+        self.archived = Some(a);
+        self
+    }
+
+    pub fn sort(mut self, s: OrderSort) -> Self {
+        self.sort = Some(s);
+        self
+    }
+
+    pub fn sort_order(mut self, o: SortOrder) -> Self {
+        self.sort_order = Some(o);
+        self
+    }
+
+    pub(crate) fn as_query_pairs(&self) -> Vec<(&str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(ref s) = self.status {
+            pairs.push(("status", s.to_string()));
+        }
+        if let Some(ref t) = self.created_after {
+            pairs.push(("created_after", t.clone()));
+        }
+        if let Some(ref t) = self.created_before {
+            pairs.push(("created_before", t.clone()));
+        }
+        if let Some(a) = self.archived {
+            pairs.push(("archived", a.to_string()));
+        }
+        if let Some(ref s) = self.sort {
+            pairs.push(("sort", s.to_string()));
+        }
+        if let Some(ref o) = self.sort_order {
+            pairs.push(("sort_order", o.to_string()));
+        }
+        pairs
+    }
+}
+
+/// Changes to apply to an order.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OrderUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<OrderStatus>,
+    /// Shipping cost in the order's currency. Setting it invoices the buyer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping: Option<f64>,
+}
+
+impl OrderUpdate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, s: OrderStatus) -> Self {
+        self.status = Some(s);
+        self
+    }
+
+    pub fn shipping(mut self, cost: f64) -> Self {
+        self.shipping = Some(cost);
+        self
+    }
+
+    /// Check the update against the order's current status.
+    ///
+    /// Uses the order's `next_status` list when Discogs provided one, and
+    /// [`OrderStatus::can_transition_to`] otherwise.
+    pub(crate) fn validate_for(&self, order: &Order) -> Result<()> {
+        if self.status.is_none() && self.shipping.is_none() {
+            return Err(DiscogsError::Configuration(
+                "order update has no changes".into(),
+            ));
+        }
+        if let Some(next) = self.status {
+            // An unknown status in `next_status` can't be sent back by name.
+            let allowed = if next == OrderStatus::Other {
+                false
+            } else if order.next_status.is_empty() {
+                order.status.can_transition_to(next)
+            } else {
+                order.next_status.contains(&next)
+            };
+            if !allowed {
+                return Err(DiscogsError::Configuration(format!(
+                    "order {} cannot move from \"{}\" to \"{next}\"",
+                    order.id, order.status
+                )));
+            }
+        }
+        if let Some(shipping) = self.shipping {
+            if order.status.is_terminal() {
+                return Err(DiscogsError::Configuration(format!(
+                    "order {} is closed (\"{}\")",
+                    order.id, order.status
+                )));
+            }
+            if !(shipping.is_finite() && shipping >= 0.0) {
+                return Err(DiscogsError::Configuration(format!(
+                    "shipping must not be negative, got {shipping}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(status: OrderStatus, next_status: Vec<OrderStatus>) -> Order {
+        Order {
+            id: "1-1".into(),
+            status,
+            next_status,
+            archived: false,
+            created: None,
+            last_activity: None,
+            items: Vec::new(),
+            total: None,
+            fee: None,
+            shipping: None,
+            shipping_address: None,
+            additional_instructions: None,
+            seller: UserSummary {
+                id: 1,
+                username: "seller".into(),
+                resource_url: None,
+            },
+            buyer: UserSummary {
+                id: 2,
+                username: "buyer".into(),
+                resource_url: None,
+            },
+            uri: None,
+            resource_url: None,
+            messages_url: None,
+        }
+    }
+
+    #[test]
+    fn test_deserialize_order() {
+        let json = r#"{
+            "id": "1-1",
+            "status": "Payment Received",
+            "next_status": ["Shipped", "Refund Sent", "Cancelled (Item Unavailable)"],
+            "fee": {"currency": "USD", "value": 2.52},
+            "created": "2011-10-21T09:25:17-07:00",
+            "items": [{
+                "id": 41578242,
+                "release": {"id": 1, "description": "Persuader, The - Stockholm (2x12\")"},
+                "price": {"currency": "USD", "value": 42.0},
+                "media_condition": "Very Good Plus (VG+)",
+                "sleeve_condition": "Generic"
+            }],
+            "shipping": {"currency": "USD", "method": "Standard", "value": 5.0},
+            "archived": false,
+            "seller": {"id": 1, "username": "example_seller"},
+            "buyer": {"id": 2, "username": "example_buyer"},
+            "total": {"currency": "USD", "value": 47.0}
+        }"#;
+        let order: Order = serde_json::from_str(json).unwrap();
+        assert_eq!(order.status, OrderStatus::PaymentReceived);
+        assert_eq!(order.next_status.len(), 3);
+        assert_eq!(
+            order.items[0].media_condition,
+            Some(Condition::VeryGoodPlus)
+        );
+        let shipping = order.shipping.unwrap();
+        assert_eq!(shipping.method.as_deref(), Some("Standard"));
+        assert_eq!(shipping.value, Money::new(5.0, Currency::Usd));
+        assert_eq!(order.total, Some(Money::new(47.0, Currency::Usd)));
+    }
+
+    #[test]
+    fn test_deserialize_order_messages() {
+        let json = r#"[
+            {
+                "type": "message",
+                "subject": "Discogs Order #1-1, Stockholm",
+                "message": "Thanks, shipping tomorrow.",
+                "from": {"id": 1, "username": "example_seller"},
+                "timestamp": "2011-11-18T15:32:42-07:00",
+                "order": {"id": "1-1"}
+            },
+            {
+                "type": "refund_sent",
+                "message": "Refund of $5.00 sent.",
+                "refund": {"amount": 5, "order": {"id": "1-1"}}
+            },
+            {"type": "feedback"}
+        ]"#;
+        let messages: Vec<OrderMessage> = serde_json::from_str(json).unwrap();
+        assert_eq!(messages[0].message_type, MessageType::Message);
+        assert_eq!(
+            messages[0].from.as_ref().unwrap().username,
+            "example_seller"
+        );
+        assert_eq!(messages[1].message_type, MessageType::RefundSent);
+        let refund = messages[1].refund.as_ref().unwrap();
+        assert_eq!(refund.amount.amount, 5.0);
+        assert_eq!(refund.amount.currency, None);
+        assert!(messages[1].from.is_none());
+        assert_eq!(messages[2].message_type, MessageType::Other);
+    }
+
+    #[test]
+    fn test_deserialize_marketplace_stats() {
+        let json = r#"{
+            "lowest_price": {"currency": "USD", "value": 2.09},
+            "num_for_sale": 26,
+            "blocked_from_sale": false
+        }"#;
+        let stats: MarketplaceStats = serde_json::from_str(json).unwrap();
+        assert_eq!(stats.lowest_price, Some(Money::new(2.09, Currency::Usd)));
+        assert_eq!(stats.num_for_sale, Some(26));
+
+        let json = r#"{"lowest_price": null, "num_for_sale": 0, "blocked_from_sale": true}"#;
+        let blocked: MarketplaceStats = serde_json::from_str(json).unwrap();
+        assert!(blocked.lowest_price.is_none());
+        assert!(blocked.blocked_from_sale);
+    }
+
+    #[test]
+    fn test_order_status_transitions() {
+        assert!(OrderStatus::NewOrder.can_transition_to(OrderStatus::InvoiceSent));
+        assert!(OrderStatus::PaymentReceived.can_transition_to(OrderStatus::Shipped));
+        assert!(OrderStatus::Shipped.can_transition_to(OrderStatus::RefundSent));
+        assert!(!OrderStatus::InvoiceSent.can_transition_to(OrderStatus::RefundSent));
+        assert!(!OrderStatus::Shipped.can_transition_to(OrderStatus::NewOrder));
+        assert!(!OrderStatus::InProgress.can_transition_to(OrderStatus::Merged));
+        assert!(!OrderStatus::Merged.can_transition_to(OrderStatus::Shipped));
+        assert!(!OrderStatus::CancelledItemUnavailable.can_transition_to(OrderStatus::InProgress));
+        assert!(!OrderStatus::Other.can_transition_to(OrderStatus::Shipped));
+        assert!(!OrderStatus::PaymentReceived.can_transition_to(OrderStatus::Other));
+        assert!(!OrderStatus::Other.is_terminal());
+    }
+
+    #[test]
+    fn test_unknown_order_status_deserializes_as_other() {
+        let status: OrderStatus = serde_json::from_str(r#""Awaiting Pickup""#).unwrap();
+        assert_eq!(status, OrderStatus::Other);
+
+        // Even when Discogs offers it, there's no name to send.
+        let offered = order(OrderStatus::PaymentReceived, vec![OrderStatus::Other]);
+        assert!(OrderUpdate::new()
+            .status(OrderStatus::Other)
+            .validate_for(&offered)
+            .is_err());
+    }
+
+    #[test]
+    fn test_order_update_validation() {
+        let new_order = order(OrderStatus::NewOrder, Vec::new());
+        assert!(OrderUpdate::new().validate_for(&new_order).is_err());
+        assert!(OrderUpdate::new()
+            .status(OrderStatus::RefundSent)
+            .validate_for(&new_order)
+            .is_err());
+        assert!(OrderUpdate::new()
+            .shipping(-1.0)
+            .validate_for(&new_order)
+            .is_err());
+        assert!(OrderUpdate::new()
+            .shipping(4.5)
+            .validate_for(&new_order)
+            .is_ok());
+
+        // The server-provided list takes precedence over the local rules.
+        let restricted = order(OrderStatus::PaymentReceived, vec![OrderStatus::Shipped]);
+        assert!(OrderUpdate::new()
+            .status(OrderStatus::Shipped)
+            .validate_for(&restricted)
+            .is_ok());
+        assert!(OrderUpdate::new()
+            .status(OrderStatus::InProgress)
+            .validate_for(&restricted)
+            .is_err());
+
+        let cancelled = order(OrderStatus::CancelledNonPayingBuyer, Vec::new());
+        assert!(OrderUpdate::new()
+            .shipping(1.0)
+            .validate_for(&cancelled)
+            .is_err());
+    }
+
+    #[test]
+    fn test_order_params() {
+        let params = OrderParams::new()
+            .status(OrderStatus::PaymentReceived)
+            .created_after("2024-01-01T00:00:00Z")
+            .archived(false)
+            .sort(OrderSort::LastActivity)
+            .sort_order(SortOrder::Desc);
+        assert_eq!(
+            params.as_query_pairs(),
+            vec![
+                ("status", "Payment Received".to_string()),
+                ("created_after", "2024-01-01T00:00:00Z".to_string()),
+                ("archived", "false".to_string()),
+                ("sort", "last_activity".to_string()),
+                ("sort_order", "desc".to_string())
+            ]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use super::{ArtistSummary, Image};
+use super::{ArtistSummary, Image, Money};
 use crate::models::release::Track;
 
 /// A master release from the Discogs API.
@@ -39,8 +39,9 @@ pub struct MasterRelease {
     pub data_quality: Option<String>,
     #[serde(default)]
     pub num_for_sale: Option<u32>,
+    /// Lowest marketplace price, in the requesting user's currency.
     #[serde(default)]
-    pub lowest_price: Option<f64>,
+    pub lowest_price: Option<Money>,
 }
 
 /// A version of a master release.
@@ -1,17 +1,51 @@
 pub mod artist;
+pub mod collection;
+pub mod inventory;
 pub mod label;
+pub mod list;
+pub mod listing;
+pub mod marketplace;
 pub mod master;
+pub mod money;
 pub mod release;
 pub mod search;
+pub mod user;
+pub mod wantlist;
 
 use serde::Deserialize;
+use std::fmt;
 
 pub use artist::{Artist, ArtistRelease};
+pub use collection::{
+    CollectionField, CollectionFolder, CollectionInstance, CollectionItem, CollectionItemsParams,
+    CollectionSort, CollectionValue, InstanceEdit, NoteValue,
+};
+pub use inventory::{
+    InventoryExport, InventoryExportRow, InventoryUpload, JobStatus, ListingChange, PollOptions,
+    UploadListing, UploadType,
+};
 pub use label::{Label, LabelRelease};
+pub use list::{List, ListEntity, ListItem, ListSummary};
+pub use listing::{
+    Condition, CreatedListing, InventoryParams, InventorySort, Listing, ListingParams,
+    ListingRelease, ListingStatus, Seller, SellerStats,
+};
+pub use marketplace::{
+    MarketplaceStats, MessageType, Order, OrderItem, OrderMessage, OrderParams, OrderRef,
+    OrderRelease, OrderShipping, OrderSort, OrderStatus, OrderUpdate, Refund, UserSummary,
+};
 pub use master::{MasterRelease, MasterVersion};
+pub use money::{Currency, Money};
+pub use release::{
+    Community, CommunityRating, Contributor, ReleaseRating, ReleaseStats, UserReleaseRating,
+};
 pub use release::{Company, Format, Identifier, LabelRef, Video};
 pub use release::{Release, Track};
 pub use search::{SearchParams, SearchResult, SearchType};
+pub use user::{
+    ContributionSort, ContributionsParams, Identity, ProfileEdit, Submission, UserProfile,
+};
+pub use wantlist::{Want, WantEdit};
 
 /// Pagination metadata returned by the Discogs API.
 #[derive(Debug, Clone, Deserialize)]
@@ -52,3 +86,49 @@ pub struct ArtistSummary {
     #[serde(default)]
     pub tracks: Option<String>,
 }
+
+/// Summary of a release embedded in collection and wantlist items.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BasicInformation {
+    pub id: u64,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub year: Option<u32>,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+    #[serde(default)]
+    pub thumb: Option<String>,
+    #[serde(default)]
+    pub cover_image: Option<String>,
+    #[serde(default)]
+    pub master_id: Option<u64>,
+    #[serde(default)]
+    pub master_url: Option<String>,
+    #[serde(default)]
+    pub artists: Vec<ArtistSummary>,
+    #[serde(default)]
+    pub labels: Vec<LabelRef>,
+    #[serde(default)]
+    pub formats: Vec<Format>,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    #[serde(default)]
+    pub styles: Vec<String>,
+}
+
+/// Sort direction for list endpoints.
+#[derive(Debug, Clone)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortOrder::Asc => write!(f, "asc"),
+            SortOrder::Desc => write!(f, "desc"),
+        }
+    }
+}
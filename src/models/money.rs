@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A currency supported by the Discogs marketplace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Currency {
+    Usd,
+    Gbp,
+    Eur,
+    Cad,
+    Aud,
+    Jpy,
+    Chf,
+    Mxn,
+    Brl,
+    Nzd,
+    Sek,
+    Zar,
+}
+
+impl Currency {
+    /// All supported currencies.
+    pub const ALL: [Currency; 12] = [
+        Currency::Usd,
+        Currency::Gbp,
+        Currency::Eur,
+        Currency::Cad,
+        Currency::Aud,
+        Currency::Jpy,
+        Currency::Chf,
+        Currency::Mxn,
+        Currency::Brl,
+        Currency::Nzd,
+        Currency::Sek,
+        Currency::Zar,
+    ];
+
+    /// The ISO 4217 code, e.g. `"USD"`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Gbp => "GBP",
+            Currency::Eur => "EUR",
+            Currency::Cad => "CAD",
+            Currency::Aud => "AUD",
+            Currency::Jpy => "JPY",
+            Currency::Chf => "CHF",
+            Currency::Mxn => "MXN",
+            Currency::Brl => "BRL",
+            Currency::Nzd => "NZD",
+            Currency::Sek => "SEK",
+            Currency::Zar => "ZAR",
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Currency symbols as they appear in formatted amounts, longest first so
+/// that e.g. `CA$` is matched before `$`.
+const SYMBOLS: &[(&str, Currency)] = &[
+    ("CA$", Currency::Cad),
+    ("MX$", Currency::Mxn),
+    ("NZ$", Currency::Nzd),
+    ("A$", Currency::Aud),
+    ("R$", Currency::Brl),
+    ("$", Currency::Usd),
+    ("£", Currency::Gbp),
+    ("€", Currency::Eur),
+    ("¥", Currency::Jpy),
+    ("kr", Currency::Sek),
+    ("R", Currency::Zar),
+];
+
+/// A monetary amount.
+///
+/// Deserializes from the shapes the API uses: `{"value": 1.5, "currency": "USD"}`
+/// (or `"curr_abbr"`), formatted strings such as `"$1,234.56"` or `"€12,50"`, and bare numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "RawMoney")]
+pub struct Money {
+    pub amount: f64,
+    /// `None` when the API reports a bare amount without saying which
+    /// currency it is in.
+    pub currency: Option<Currency>,
+}
+
+impl Money {
+    pub fn new(amount: f64, currency: Currency) -> Self {
+        Self {
+            amount,
+            currency: Some(currency),
+        }
+    }
+
+    /// Parse a formatted amount such as `"$1,234.56"`, `"€12,50"` or `"CHF 10.00"`.
+    ///
+    /// Negative amounts may put the minus sign before or after the currency
+    /// symbol, as in `"-$5.00"` or `"$-5.00"`.
+    pub fn parse(s: &str) -> Option<Money> {
+        let s = s.trim();
+        let (sign_first, s) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, s),
+        };
+        let (currency, number) = Currency::ALL
+            .iter()
+            .map(|c| (c.code(), *c))
+            .chain(SYMBOLS.iter().copied())
+            .find_map(|(symbol, currency)| {
+                s.strip_prefix(symbol)
+                    .or_else(|| s.strip_suffix(symbol))
+                    .map(|rest| (currency, rest))
+            })?;
+        let (sign_after, number) = match number.trim_start().strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, number),
+        };
+        if sign_first && sign_after {
+            return None;
+        }
+        let sign = if sign_first || sign_after { -1.0 } else { 1.0 };
+        Some(Money::new(sign * parse_amount(number)?, currency))
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.currency {
+            Some(currency) => write!(f, "{:.2} {currency}", self.amount),
+            None => write!(f, "{:.2}", self.amount),
+        }
+    }
+}
+
+/// Parse the numeric part of a formatted amount, accepting either `,` or `.`
+/// as the decimal separator. A final separator followed by one or two digits
+/// is treated as the decimal point; all other separators group thousands.
+/// Signs are handled by [`Money::parse`], so only digits and separators are
+/// accepted here.
+fn parse_amount(s: &str) -> Option<f64> {
+    let digits: String = s
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '\u{a0}')
+        .collect();
+    if !digits.chars().any(|c| c.is_ascii_digit())
+        || !digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ',' || c == '.')
+    {
+        return None;
+    }
+
+    let normalized = match digits.rfind([',', '.']) {
+        Some(idx) if (1..=2).contains(&(digits.len() - idx - 1)) => {
+            let (whole, fraction) = digits.split_at(idx);
+            format!("{}.{}", whole.replace([',', '.'], ""), &fraction[1..])
+        }
+        _ => digits.replace([',', '.'], ""),
+    };
+    normalized.parse().ok()
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawMoney {
+    Amount(f64),
+    Formatted(String),
+    Priced { value: f64, currency: Currency },
+    Abbreviated { value: f64, curr_abbr: Currency },
+}
+
+impl TryFrom<RawMoney> for Money {
+    type Error = String;
+
+    fn try_from(raw: RawMoney) -> std::result::Result<Self, Self::Error> {
+        match raw {
+            RawMoney::Amount(amount) => Ok(Money {
+                amount,
+                currency: None,
+            }),
+            RawMoney::Formatted(s) => {
+                Money::parse(&s).ok_or_else(|| format!("unrecognized money amount: {s:?}"))
+            }
+            RawMoney::Priced { value, currency } => Ok(Money::new(value, currency)),
+            RawMoney::Abbreviated { value, curr_abbr } => Ok(Money::new(value, curr_abbr)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formatted_amounts() {
+        assert_eq!(
+            Money::parse("$1,234.56"),
+            Some(Money::new(1234.56, Currency::Usd))
+        );
+        assert_eq!(
+            Money::parse("€12,50"),
+            Some(Money::new(12.5, Currency::Eur))
+        );
+        assert_eq!(
+            Money::parse("€1.234,50"),
+            Some(Money::new(1234.5, Currency::Eur))
+        );
+        assert_eq!(
+            Money::parse("¥1,234"),
+            Some(Money::new(1234.0, Currency::Jpy))
+        );
+        assert_eq!(
+            Money::parse("CA$20.00"),
+            Some(Money::new(20.0, Currency::Cad))
+        );
+        assert_eq!(
+            Money::parse("CHF 10.00"),
+            Some(Money::new(10.0, Currency::Chf))
+        );
+        assert_eq!(
+            Money::parse("12,50 €"),
+            Some(Money::new(12.5, Currency::Eur))
+        );
+        assert_eq!(Money::parse("12.50"), None);
+        assert_eq!(Money::parse("$"), None);
+    }
+
+    #[test]
+    fn test_parse_negative_amounts() {
+        assert_eq!(
+            Money::parse("-$5.00"),
+            Some(Money::new(-5.0, Currency::Usd))
+        );
+        assert_eq!(
+            Money::parse("$-5.00"),
+            Some(Money::new(-5.0, Currency::Usd))
+        );
+        assert_eq!(
+            Money::parse("-€1.234,50"),
+            Some(Money::new(-1234.5, Currency::Eur))
+        );
+        assert_eq!(
+            Money::parse("-12,50 €"),
+            Some(Money::new(-12.5, Currency::Eur))
+        );
+        assert_eq!(
+            Money::parse("CHF -10.00"),
+            Some(Money::new(-10.0, Currency::Chf))
+        );
+        assert_eq!(Money::parse("-$-5.00"), None);
+        assert_eq!(Money::parse("$--5.00"), None);
+        assert_eq!(Money::parse("$5.00-"), None);
+    }
+
+    #[test]
+    fn test_deserialize_money_shapes() {
+        let priced: Money = serde_json::from_str(r#"{"value": 3.5, "currency": "GBP"}"#).unwrap();
+        assert_eq!(priced, Money::new(3.5, Currency::Gbp));
+
+        let abbreviated: Money =
+            serde_json::from_str(r#"{"value": 3.5, "curr_abbr": "GBP", "formatted": "£3.50"}"#)
+                .unwrap();
+        assert_eq!(abbreviated, Money::new(3.5, Currency::Gbp));
+
+        let formatted: Money = serde_json::from_str(r#""£3.50""#).unwrap();
+        assert_eq!(formatted, Money::new(3.5, Currency::Gbp));
+
+        let bare: Money = serde_json::from_str("3.5").unwrap();
+        assert_eq!(bare.currency, None);
+
+        assert!(serde_json::from_str::<Money>(r#""lots""#).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use super::{ArtistSummary, Image};
+use super::{ArtistSummary, Image, Money};
 
 /// A full release resource from the Discogs API.
 #[derive(Debug, Clone, Deserialize)]
@@ -57,7 +57,77 @@ pub struct Release {
     #[serde(default)]
     pub num_for_sale: Option<u32>,
     #[serde(default)]
-    pub lowest_price: Option<f64>,
+    pub community: Option<Community>,
+    /// Lowest marketplace price, in the requesting user's currency unless
+    /// fetched with [`DiscogsClient::release_with_currency`](crate::DiscogsClient::release_with_currency).
+    #[serde(default)]
+    pub lowest_price: Option<Money>,
+}
+
+/// Community data for a release: collection counts, ratings and who
+/// submitted it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Community {
+    /// Number of users with the release in their collection.
+    #[serde(default)]
+    pub have: u32,
+    /// Number of users with the release in their wantlist.
+    #[serde(default)]
+    pub want: u32,
+    #[serde(default)]
+    pub rating: Option<CommunityRating>,
+    #[serde(default)]
+    pub submitter: Option<Contributor>,
+    #[serde(default)]
+    pub contributors: Vec<Contributor>,
+    #[serde(default)]
+    pub data_quality: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+/// The average community rating of a release.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommunityRating {
+    #[serde(default)]
+    pub count: u32,
+    #[serde(default)]
+    pub average: f64,
+}
+
+/// A user who submitted or edited release data.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Contributor {
+    pub username: String,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+}
+
+/// A release's community rating, from `/releases/{id}/rating`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseRating {
+    pub release_id: u64,
+    pub rating: CommunityRating,
+}
+
+/// A single user's rating of a release.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserReleaseRating {
+    pub release_id: u64,
+    pub username: String,
+    /// Rating from 1 to 5, or 0 if the user hasn't rated the release.
+    pub rating: u8,
+}
+
+/// How many users have and want a release, from `/releases/{id}/stats`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseStats {
+    #[serde(default)]
+    pub num_have: u32,
+    #[serde(default)]
+    pub num_want: u32,
+    #[serde(default)]
+    pub is_offensive: bool,
 }
 
 /// A track in a release's tracklist.
@@ -194,4 +264,36 @@ mod tests {
         assert_eq!(track.title.as_deref(), Some("Test Track"));
         assert_eq!(track.duration.as_deref(), Some("5:30"));
     }
+
+    #[test]
+    fn test_deserialize_community() {
+        let json = r#"{
+            "id": 249504,
+            "community": {
+                "have": 252,
+                "want": 42,
+                "rating": {"count": 45, "average": 3.42},
+                "submitter": {"username": "memory", "resource_url": "https://api.discogs.com/users/memory"},
+                "contributors": [{"username": "memory"}, {"username": "_80_"}],
+                "data_quality": "Correct",
+                "status": "Accepted"
+            }
+        }"#;
+        let release: Release = serde_json::from_str(json).unwrap();
+        let community = release.community.unwrap();
+        assert_eq!(community.have, 252);
+        assert_eq!(community.rating.unwrap().average, 3.42);
+        assert_eq!(community.submitter.unwrap().username, "memory");
+        assert_eq!(community.contributors.len(), 2);
+        assert_eq!(community.data_quality.as_deref(), Some("Correct"));
+    }
+
+    #[test]
+    fn test_deserialize_lowest_price() {
+        let json = r#"{"id": 1, "num_for_sale": 4, "lowest_price": 12.5}"#;
+        let release: Release = serde_json::from_str(json).unwrap();
+        let price = release.lowest_price.unwrap();
+        assert_eq!(price.amount, 12.5);
+        assert_eq!(price.currency, None);
+    }
 }
@@ -0,0 +1,274 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::{Artist, Currency, Label, Release, SortOrder};
+
+/// The authenticated user's identity, from `/oauth/identity`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Identity {
+    pub id: u64,
+    pub username: String,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+    #[serde(default)]
+    pub consumer_name: Option<String>,
+}
+
+/// A user's public profile. Private fields such as `email` are only
+/// present when viewing your own profile.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserProfile {
+    pub id: u64,
+    pub username: String,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+    #[serde(default)]
+    pub uri: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub home_page: Option<String>,
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub registered: Option<String>,
+    /// The currency the user buys and sells in.
+    #[serde(default)]
+    pub curr_abbr: Option<Currency>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub banner_url: Option<String>,
+    #[serde(default)]
+    pub rank: Option<f64>,
+    #[serde(default)]
+    pub rating_avg: Option<f64>,
+    #[serde(default)]
+    pub num_pending: Option<u32>,
+    #[serde(default)]
+    pub num_for_sale: Option<u32>,
+    #[serde(default)]
+    pub num_lists: Option<u32>,
+    #[serde(default)]
+    pub num_collection: Option<u32>,
+    #[serde(default)]
+    pub num_wantlist: Option<u32>,
+    #[serde(default)]
+    pub releases_contributed: Option<u32>,
+    #[serde(default)]
+    pub releases_rated: Option<u32>,
+    #[serde(default)]
+    pub buyer_rating: Option<f64>,
+    #[serde(default)]
+    pub buyer_rating_stars: Option<f64>,
+    #[serde(default)]
+    pub buyer_num_ratings: Option<u32>,
+    #[serde(default)]
+    pub seller_rating: Option<f64>,
+    #[serde(default)]
+    pub seller_rating_stars: Option<f64>,
+    #[serde(default)]
+    pub seller_num_ratings: Option<u32>,
+    #[serde(default)]
+    pub inventory_url: Option<String>,
+    #[serde(default)]
+    pub collection_folders_url: Option<String>,
+    #[serde(default)]
+    pub collection_fields_url: Option<String>,
+    #[serde(default)]
+    pub wantlist_url: Option<String>,
+}
+
+/// Fields to change when editing a user profile. Unset fields are left as-is.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProfileEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub home_page: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curr_abbr: Option<Currency>,
+}
+
+impl ProfileEdit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, n: impl Into<String>) -> Self {
+        self.name = Some(n.into());
+        self
+    }
+
+    pub fn home_page(mut self, h: impl Into<String>) -> Self {
+        self.home_page = Some(h.into());
+        self
+    }
+
+    pub fn location(mut self, l: impl Into<String>) -> Self {
+        self.location = Some(l.into());
+        self
+    }
+
+    pub fn profile(mut self, p: impl Into<String>) -> Self {
+        self.profile = Some(p.into());
+        self
+    }
+
+    pub fn curr_abbr(mut self, c: Currency) -> Self {
+        self.curr_abbr = Some(c);
+        self
+    }
+}
+
+/// Field to sort a user's contributions by.
+#[derive(Debug, Clone)]
+pub enum ContributionSort {
+    Label,
+    Artist,
+    Title,
+    Catno,
+    Format,
+    Rating,
+    Year,
+    Added,
+}
+
+impl fmt::Display for ContributionSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContributionSort::Label => write!(f, "label"),
+            ContributionSort::Artist => write!(f, "artist"),
+            ContributionSort::Title => write!(f, "title"),
+            ContributionSort::Catno => write!(f, "catno"),
+            ContributionSort::Format => write!(f, "format"),
+            ContributionSort::Rating => write!(f, "rating"),
+            ContributionSort::Year => write!(f, "year"),
+            ContributionSort::Added => write!(f, "added"),
+        }
+    }
+}
+
+/// Parameters for listing a user's contributions.
+#[derive(Debug, Clone, Default)]
+pub struct ContributionsParams {
+    pub sort: Option<ContributionSort>,
+    pub sort_order: Option<SortOrder>,
+}
+
+impl ContributionsParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sort(mut self, s: ContributionSort) -> Self {
+        self.sort = Some(s);
+        self
+    }
+
+    pub fn sort_order(mut self, o: SortOrder) -> Self {
+        self.sort_order = Some(o);
+        self
+    }
+
+    pub(crate) fn as_query_pairs(&self) -> Vec<(&str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(ref s) = self.sort {
+            pairs.push(("sort", s.to_string()));
+        }
+        if let Some(ref o) = self.sort_order {
+            pairs.push(("sort_order", o.to_string()));
+        }
+        pairs
+    }
+}
+
+/// An edit a user submitted to the database.
+#[derive(Debug, Clone)]
+pub enum Submission {
+    Artist(Artist),
+    Label(Label),
+    Release(Box<Release>),
+}
+
+/// The `submissions` object, which groups a page of submissions by type.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct SubmissionGroups {
+    #[serde(default)]
+    artists: Vec<Artist>,
+    #[serde(default)]
+    labels: Vec<Label>,
+    #[serde(default)]
+    releases: Vec<Release>,
+}
+
+impl SubmissionGroups {
+    /// Flatten the groups into one list: artists, then labels, then releases.
+    pub(crate) fn into_vec(self) -> Vec<Submission> {
+        self.artists
+            .into_iter()
+            .map(Submission::Artist)
+            .chain(self.labels.into_iter().map(Submission::Label))
+            .chain(
+                self.releases
+                    .into_iter()
+                    .map(|r| Submission::Release(Box::new(r))),
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_user_profile() {
+        let json = r#"{
+            "id": 1578108,
+            "username": "example",
+            "name": "Example User",
+            "location": "Portland, OR",
+            "num_collection": 120,
+            "rating_avg": 3.5,
+            "curr_abbr": "USD"
+        }"#;
+        let profile: UserProfile = serde_json::from_str(json).unwrap();
+        assert_eq!(profile.username, "example");
+        assert_eq!(profile.num_collection, Some(120));
+        assert_eq!(profile.email, None);
+        assert_eq!(profile.curr_abbr, Some(Currency::Usd));
+    }
+
+    #[test]
+    fn test_profile_edit_skips_unset_fields() {
+        let edit = ProfileEdit::new().location("Berlin");
+        let json = serde_json::to_value(&edit).unwrap();
+        assert_eq!(json, serde_json::json!({"location": "Berlin"}));
+
+        let edit = ProfileEdit::new().curr_abbr(Currency::Eur);
+        let json = serde_json::to_value(&edit).unwrap();
+        assert_eq!(json, serde_json::json!({"curr_abbr": "EUR"}));
+    }
+
+    #[test]
+    fn test_deserialize_submission_groups() {
+        let json = r#"{
+            "artists": [{"id": 1, "name": "The Persuader"}],
+            "releases": [{"id": 2, "title": "Stockholm"}, {"id": 3, "title": "Östermalm"}]
+        }"#;
+        let groups: SubmissionGroups = serde_json::from_str(json).unwrap();
+        let submissions = groups.into_vec();
+        assert_eq!(submissions.len(), 3);
+        assert!(matches!(&submissions[0], Submission::Artist(a) if a.name == "The Persuader"));
+        assert!(matches!(&submissions[2], Submission::Release(r) if r.id == 3));
+    }
+}
@@ -0,0 +1,91 @@
+use serde::Deserialize;
+
+use super::BasicInformation;
+use crate::error::{DiscogsError, Result};
+
+/// A release in a user's wantlist.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Want {
+    /// The release ID.
+    pub id: u64,
+    #[serde(default)]
+    pub rating: u8,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub date_added: Option<String>,
+    #[serde(default)]
+    pub resource_url: Option<String>,
+    pub basic_information: BasicInformation,
+}
+
+/// Notes and rating to set on a wantlist entry.
+#[derive(Debug, Clone, Default)]
+pub struct WantEdit {
+    pub notes: Option<String>,
+    /// Rating from 1 to 5, or 0 to clear it.
+    pub rating: Option<u8>,
+}
+
+impl WantEdit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn notes(mut self, n: impl Into<String>) -> Self {
+        self.notes = Some(n.into());
+        self
+    }
+
+    pub fn rating(mut self, r: u8) -> Self {
+        self.rating = Some(r);
+        self
+    }
+
+    pub(crate) fn as_query_pairs(&self) -> Result<Vec<(&str, String)>> {
+        let mut pairs = Vec::new();
+        if let Some(ref notes) = self.notes {
+            pairs.push(("notes", notes.clone()));
+        }
+        if let Some(rating) = self.rating {
+            if rating > 5 {
+                return Err(DiscogsError::Configuration(format!(
+                    "rating must be between 0 and 5, got {rating}"
+                )));
+            }
+            pairs.push(("rating", rating.to_string()));
+        }
+        Ok(pairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_want() {
+        let json = r#"{
+            "id": 1867708,
+            "rating": 4,
+            "notes": "My favorite",
+            "basic_information": {"id": 1867708, "title": "Year Zero", "year": 2007}
+        }"#;
+        let want: Want = serde_json::from_str(json).unwrap();
+        assert_eq!(want.id, 1867708);
+        assert_eq!(want.notes.as_deref(), Some("My favorite"));
+        assert_eq!(want.basic_information.year, Some(2007));
+    }
+
+    #[test]
+    fn test_want_edit_rejects_invalid_rating() {
+        assert!(WantEdit::new().rating(9).as_query_pairs().is_err());
+        assert_eq!(
+            WantEdit::new()
+                .notes("VG+ or better")
+                .as_query_pairs()
+                .unwrap(),
+            vec![("notes", "VG+ or better".to_string())]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use percent_encoding::percent_decode_str;
+use reqwest::Client;
+
+use crate::auth::{build_oauth_header, percent_encode};
+use crate::client::check_status;
+use crate::error::{DiscogsError, Result};
+
+const BASE_URL: &str = "https://api.discogs.com";
+const AUTHORIZE_URL: &str = "https://www.discogs.com/oauth/authorize";
+
+/// Drives the OAuth 1.0a three-legged authorization flow.
+///
+/// 1. [`request_token`](Self::request_token) fetches a temporary request token.
+/// 2. Send the user to [`authorize_url`](Self::authorize_url) to approve access.
+/// 3. [`access_token`](Self::access_token) exchanges the verifier Discogs hands back
+///    for long-lived credentials, which can be passed to
+///    [`ClientBuilder::oauth_credentials`](crate::ClientBuilder::oauth_credentials).
+pub struct OAuthFlow {
+    http: Client,
+    consumer_key: String,
+    consumer_secret: String,
+    base_url: String,
+    authorize_url: String,
+}
+
+/// Builder for creating an [`OAuthFlow`].
+pub struct OAuthFlowBuilder {
+    user_agent: Option<String>,
+    consumer: Option<(String, String)>,
+    base_url: String,
+    authorize_url: String,
+}
+
+/// A temporary token returned by the request-token step.
+#[derive(Debug, Clone)]
+pub struct RequestToken {
+    pub token: String,
+    pub token_secret: String,
+    /// Whether Discogs acknowledged the `oauth_callback` URL.
+    pub callback_confirmed: bool,
+}
+
+/// Long-lived OAuth credentials for an authorized user.
+#[derive(Debug, Clone)]
+pub struct OAuthCredentials {
+    pub consumer_key: String,
+    pub consumer_secret: String,
+    pub token: String,
+    pub token_secret: String,
+}
+
+impl OAuthFlowBuilder {
+    fn new() -> Self {
+        Self {
+            user_agent: None,
+            consumer: None,
+            base_url: BASE_URL.to_string(),
+            authorize_url: AUTHORIZE_URL.to_string(),
+        }
+    }
+
+    /// Override the base URL. For testing only.
+    #[doc(hidden)]
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Override the authorization page URL. For testing only.
+    #[doc(hidden)]
+    pub fn authorize_url(mut self, url: impl Into<String>) -> Self {
+        self.authorize_url = url.into();
+        self
+    }
+
+    /// Set the User-Agent header (required by Discogs API).
+    pub fn user_agent(mut self, ua: impl Into<String>) -> Self {
+        self.user_agent = Some(ua.into());
+        self
+    }
+
+    /// Set the application's consumer key and secret.
+    pub fn consumer(
+        mut self,
+        consumer_key: impl Into<String>,
+        consumer_secret: impl Into<String>,
+    ) -> Self {
+        self.consumer = Some((consumer_key.into(), consumer_secret.into()));
+        self
+    }
+
+    /// Build the flow.
+    pub fn build(self) -> Result<OAuthFlow> {
+        let user_agent = self.user_agent.ok_or_else(|| {
+            DiscogsError::Configuration("User-Agent is required by the Discogs API".into())
+        })?;
+
+        if user_agent.is_empty() {
+            return Err(DiscogsError::Configuration(
+                "User-Agent must not be empty".into(),
+            ));
+        }
+
+        let (consumer_key, consumer_secret) = self.consumer.ok_or_else(|| {
+            DiscogsError::Configuration("consumer key and secret are required".into())
+        })?;
+
+        let http = Client::builder()
+            .user_agent(&user_agent)
+            .build()
+            .map_err(DiscogsError::Http)?;
+
+        Ok(OAuthFlow {
+            http,
+            consumer_key,
+            consumer_secret,
+            base_url: self.base_url,
+            authorize_url: self.authorize_url,
+        })
+    }
+}
+
+impl OAuthFlow {
+    /// Create a new flow builder.
+    pub fn builder() -> OAuthFlowBuilder {
+        OAuthFlowBuilder::new()
+    }
+
+    /// Fetch a request token. Discogs redirects the user to `callback_url`
+    /// after authorization; pass `"oob"` to have the verifier shown instead.
+    pub async fn request_token(&self, callback_url: &str) -> Result<RequestToken> {
+        let url = format!("{}/oauth/request_token", self.base_url);
+        let header = build_oauth_header(
+            &self.consumer_key,
+            &self.consumer_secret,
+            None,
+            "",
+            &[("oauth_callback", callback_url)],
+            "GET",
+            &url,
+        );
+
+        let response = self
+            .http
+            .get(&url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .header("Authorization", header)
+            .send()
+            .await?;
+        let body = check_status(response).await?.text().await?;
+        let params = parse_form(&body);
+
+        let (token, token_secret) = token_pair(&params)?;
+        Ok(RequestToken {
+            token,
+            token_secret,
+            callback_confirmed: form_value(&params, "oauth_callback_confirmed") == Some("true"),
+        })
+    }
+
+    /// The URL the user must visit to approve the request token.
+    pub fn authorize_url(&self, request_token: &RequestToken) -> String {
+        format!(
+            "{}?oauth_token={}",
+            self.authorize_url,
+            percent_encode(&request_token.token)
+        )
+    }
+
+    /// Exchange an approved request token and its verifier for access credentials.
+    pub async fn access_token(
+        &self,
+        request_token: &RequestToken,
+        verifier: &str,
+    ) -> Result<OAuthCredentials> {
+        let url = format!("{}/oauth/access_token", self.base_url);
+        let header = build_oauth_header(
+            &self.consumer_key,
+            &self.consumer_secret,
+            Some(&request_token.token),
+            &request_token.token_secret,
+            &[("oauth_verifier", verifier)],
+            "POST",
+            &url,
+        );
+
+        let response = self
+            .http
+            .post(&url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .header("Authorization", header)
+            .send()
+            .await?;
+        let body = check_status(response).await?.text().await?;
+        let params = parse_form(&body);
+
+        let (token, token_secret) = token_pair(&params)?;
+        Ok(OAuthCredentials {
+            consumer_key: self.consumer_key.clone(),
+            consumer_secret: self.consumer_secret.clone(),
+            token,
+            token_secret,
+        })
+    }
+}
+
+/// Parse an `application/x-www-form-urlencoded` body into key/value pairs.
+pub(crate) fn parse_form(body: &str) -> Vec<(String, String)> {
+    let decode = |s: &str| {
+        percent_decode_str(&s.replace('+', " "))
+            .decode_utf8_lossy()
+            .into_owned()
+    };
+    body.trim()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            let key = parts.next().unwrap_or("");
+            let value = parts.next().unwrap_or("");
+            (decode(key), decode(value))
+        })
+        .collect()
+}
+
+pub(crate) fn form_value<'a>(params: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+}
+
+/// Extract `oauth_token` and `oauth_token_secret` from a token response.
+fn token_pair(params: &[(String, String)]) -> Result<(String, String)> {
+    match (
+        form_value(params, "oauth_token"),
+        form_value(params, "oauth_token_secret"),
+    ) {
+        (Some(token), Some(secret)) => Ok((token.to_string(), secret.to_string())),
+        _ => Err(DiscogsError::OAuth(
+            "token response is missing oauth_token or oauth_token_secret".into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_requires_consumer() {
+        assert!(matches!(
+            OAuthFlow::builder().user_agent("TestApp/1.0").build(),
+            Err(DiscogsError::Configuration(_))
+        ));
+    }
+
+    #[test]
+    fn parse_form_decodes_values() {
+        let params = parse_form("oauth_token=a%2Bb&oauth_token_secret=c+d&flag=true\n");
+        assert_eq!(form_value(&params, "oauth_token"), Some("a+b"));
+        assert_eq!(form_value(&params, "oauth_token_secret"), Some("c d"));
+        assert_eq!(form_value(&params, "flag"), Some("true"));
+    }
+
+    #[test]
+    fn token_pair_rejects_incomplete_response() {
+        let params = parse_form("oauth_token=abc");
+        assert!(matches!(token_pair(&params), Err(DiscogsError::OAuth(_))));
+    }
+}
@@ -1,4 +1,7 @@
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, Visitor};
 use serde::Deserialize;
+use std::fmt;
+use std::marker::PhantomData;
 
 use crate::models::PaginationInfo;
 
@@ -85,22 +88,59 @@ pub(crate) struct PaginatedResponse<T> {
     pub data: PaginatedData<T>,
 }
 
-/// Captures the varying key names used by different endpoints.
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
-pub(crate) enum PaginatedData<T> {
-    Releases { releases: Vec<T> },
-    Versions { versions: Vec<T> },
-    Results { results: Vec<T> },
-}
+/// Keys under which paginated endpoints return their items. Supporting a
+/// new endpoint only requires adding its key here.
+const ITEM_KEYS: &[&str] = &[
+    "releases",
+    "versions",
+    "results",
+    "wants",
+    "listings",
+    "orders",
+    "messages",
+    "lists",
+    "contributions",
+    "items",
+];
+
+/// Captures the items of a paginated response, whichever of [`ITEM_KEYS`]
+/// the endpoint uses.
+#[derive(Debug)]
+pub(crate) struct PaginatedData<T>(Vec<T>);
 
 impl<T> PaginatedData<T> {
     pub fn into_vec(self) -> Vec<T> {
-        match self {
-            PaginatedData::Releases { releases } => releases,
-            PaginatedData::Versions { versions } => versions,
-            PaginatedData::Results { results } => results,
+        self.0
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for PaginatedData<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ItemsVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for ItemsVisitor<T> {
+            type Value = PaginatedData<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a map with one of the keys {ITEM_KEYS:?}")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut items = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    if items.is_none() && ITEM_KEYS.contains(&key.as_str()) {
+                        items = Some(map.next_value()?);
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+                items
+                    .map(PaginatedData)
+                    .ok_or_else(|| de::Error::custom(format!("missing one of {ITEM_KEYS:?}")))
+            }
         }
+
+        deserializer.deserialize_map(ItemsVisitor(PhantomData))
     }
 }
 
@@ -154,7 +194,10 @@ mod tests {
     fn pagination_params_as_query_pairs() {
         let params = PaginationParams::new(3, 25);
         let pairs = params.as_query_pairs();
-        assert_eq!(pairs, vec![("page", "3".to_string()), ("per_page", "25".to_string())]);
+        assert_eq!(
+            pairs,
+            vec![("page", "3".to_string()), ("per_page", "25".to_string())]
+        );
     }
 
     #[test]
@@ -163,4 +206,20 @@ mod tests {
         assert_eq!(params.page, 1);
         assert_eq!(params.per_page, 50);
     }
+
+    #[test]
+    fn paginated_response_finds_item_key() {
+        let json = r#"{
+            "pagination": {"page": 1, "pages": 1, "per_page": 50, "items": 2},
+            "wants": [1, 2]
+        }"#;
+        let response: PaginatedResponse<u32> = serde_json::from_str(json).unwrap();
+        assert_eq!(response.data.into_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    fn paginated_response_requires_item_key() {
+        let json = r#"{"pagination": {"page": 1, "pages": 1}, "other": []}"#;
+        assert!(serde_json::from_str::<PaginatedResponse<u32>>(json).is_err());
+    }
 }
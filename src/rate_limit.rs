@@ -42,6 +42,14 @@ impl RateLimiter {
         }
     }
 
+    /// Empty the bucket after the server reports the limit was hit, so that
+    /// subsequent requests wait for tokens to refill.
+    pub async fn drain(&self) {
+        let mut inner = self.inner.lock().await;
+        inner.tokens = 0.0;
+        inner.last_refill = Instant::now();
+    }
+
     /// Sync the limiter with server-reported usage from the
     /// `X-Discogs-Ratelimit-Used` and `X-Discogs-Ratelimit` headers.
     pub async fn sync_from_headers(&self, used: u32, limit: u32) {
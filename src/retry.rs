@@ -0,0 +1,174 @@
+use rand::Rng;
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// When and how to retry failed requests.
+///
+/// Clients don't retry unless given a policy with
+/// [`ClientBuilder::retry_policy`](crate::ClientBuilder::retry_policy).
+/// Retries apply to every request the client sends, including writes.
+/// Requests that create something, such as a listing, a collection folder
+/// or an inventory export, are the exception: repeating one the server
+/// already acted on would create a duplicate, so they are only retried if
+/// rate limited or if the connection failed before they were sent.
+///
+/// ```
+/// use discogger::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .base_delay(Duration::from_millis(500))
+///     .retry_statuses([429, 503]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts per request, including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry. Later retries double it.
+    pub base_delay: Duration,
+    /// Upper bound on the delay between attempts, unless the server asks
+    /// for longer with `Retry-After`.
+    pub max_delay: Duration,
+    /// Randomize each delay to between half and all of its computed value.
+    pub jitter: bool,
+    /// Response statuses that are retried.
+    pub retry_statuses: Vec<u16>,
+    /// Retry requests that fail to connect or time out.
+    pub retry_connection_errors: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retry_statuses: vec![429, 500, 502, 503, 504],
+            retry_connection_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn retry_statuses(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.retry_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    pub fn retry_connection_errors(mut self, retry: bool) -> Self {
+        self.retry_connection_errors = retry;
+        self
+    }
+
+    /// Whether a response with `status` should be retried.
+    pub(crate) fn retries_status(&self, status: StatusCode) -> bool {
+        self.retry_statuses.contains(&status.as_u16())
+    }
+
+    /// Whether a request that failed with `error` should be retried.
+    pub(crate) fn retries_error(&self, error: &reqwest::Error) -> bool {
+        self.retry_connection_errors && (error.is_connect() || error.is_timeout())
+    }
+
+    /// How long to wait after failed attempt number `attempt` (starting at
+    /// 1), or `None` if no attempts are left. A server-provided
+    /// `Retry-After` takes precedence over the computed backoff.
+    pub(crate) fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        if let Some(retry_after) = retry_after {
+            return Some(retry_after);
+        }
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+        if self.jitter {
+            Some(backoff.mul_f64(rand::rng().random_range(0.5..=1.0)))
+        } else {
+            Some(backoff)
+        }
+    }
+}
+
+/// Parse a `Retry-After` header given in seconds.
+pub(crate) fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::new()
+            .max_attempts(6)
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .jitter(false);
+        let delays: Vec<_> = (1..=6).map(|attempt| policy.delay(attempt, None)).collect();
+        assert_eq!(
+            delays,
+            vec![
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(5)),
+                Some(Duration::from_secs(5)),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retry_after_overrides_backoff() {
+        let policy = RetryPolicy::new().max_delay(Duration::from_secs(5));
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(60))),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(policy.delay(3, Some(Duration::from_secs(60))), None);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_half_to_full_delay() {
+        let policy = RetryPolicy::new().base_delay(Duration::from_secs(2));
+        for _ in 0..100 {
+            let delay = policy.delay(1, None).unwrap();
+            assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! SQLite-backed response cache.
+//!
+//! Enabled with the `sqlite-cache` feature flag.
+
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::cache::{Cache, CacheEntry};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS responses (
+        key TEXT PRIMARY KEY,
+        body BLOB NOT NULL,
+        etag TEXT,
+        last_modified TEXT,
+        expires_at INTEGER NOT NULL,
+        stored_at INTEGER NOT NULL,
+        size INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS responses_stored_at ON responses (stored_at);
+";
+
+/// A cache storing responses, including images fetched with
+/// [`download_image`](crate::DiscogsClient::download_image), in a SQLite
+/// database, so that they survive between runs.
+///
+/// Entries older than `max_age` are never returned, and are evicted along
+/// with the oldest entries once the bodies stored add up to more than
+/// `max_size` bytes. Eviction runs whenever an entry is stored.
+///
+/// Queries run synchronously on the calling task, behind a mutex shared by
+/// all requests; see [`Cache`] for what that means on a busy runtime.
+pub struct SqliteCache {
+    conn: Mutex<Connection>,
+    max_age: Option<Duration>,
+    max_size: Option<u64>,
+}
+
+impl SqliteCache {
+    /// Open or create the cache database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// Create a cache held in memory, mostly useful for testing.
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            max_age: None,
+            max_size: None,
+        })
+    }
+
+    /// Evict entries stored longer than `max_age` ago.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Evict the oldest entries once their bodies total more than
+    /// `max_size` bytes.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// The total size of the bodies stored, in bytes.
+    pub fn size(&self) -> rusqlite::Result<u64> {
+        self.conn.lock().unwrap().query_row(
+            "SELECT COALESCE(SUM(size), 0) FROM responses",
+            [],
+            |row| row.get(0),
+        )
+    }
+
+    fn read(&self, key: &str, now: SystemTime) -> rusqlite::Result<Option<CacheEntry>> {
+        // Entries past `max_age` may still be stored if nothing was written
+        // since they expired.
+        let cutoff = self.cutoff(now).map_or(0, to_unix);
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT body, etag, last_modified, expires_at FROM responses
+                 WHERE key = ?1 AND stored_at >= ?2",
+                params![key, cutoff],
+                |row| {
+                    Ok(CacheEntry {
+                        body: row.get::<_, Vec<u8>>(0)?.into(),
+                        etag: row.get(1)?,
+                        last_modified: row.get(2)?,
+                        expires_at: from_unix(row.get(3)?),
+                    })
+                },
+            )
+            .optional()
+    }
+
+    fn write(&self, key: &str, entry: &CacheEntry, now: SystemTime) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO responses
+                (key, body, etag, last_modified, expires_at, stored_at, size)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                key,
+                &entry.body[..],
+                entry.etag,
+                entry.last_modified,
+                to_unix(entry.expires_at),
+                to_unix(now),
+                entry.body.len() as u64,
+            ],
+        )?;
+        self.evict(&conn, now)
+    }
+
+    /// When entries stored before now are too old to use.
+    fn cutoff(&self, now: SystemTime) -> Option<SystemTime> {
+        self.max_age
+            .map(|max_age| now.checked_sub(max_age).unwrap_or(UNIX_EPOCH))
+    }
+
+    fn evict(&self, conn: &Connection, now: SystemTime) -> rusqlite::Result<()> {
+        if let Some(cutoff) = self.cutoff(now) {
+            conn.execute(
+                "DELETE FROM responses WHERE stored_at < ?1",
+                [to_unix(cutoff)],
+            )?;
+        }
+        if let Some(max_size) = self.max_size {
+            // Keep the newest entries that fit in `max_size`.
+            conn.execute(
+                "DELETE FROM responses WHERE key IN (
+                    SELECT key FROM (
+                        SELECT key, SUM(size) OVER (ORDER BY stored_at DESC, rowid DESC) AS total
+                        FROM responses
+                    ) WHERE total > ?1
+                )",
+                [max_size],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Cache for SqliteCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        self.read(key, SystemTime::now()).ok().flatten()
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry) {
+        let _ = self.write(key, &entry, SystemTime::now());
+    }
+
+    fn remove(&self, key: &str) {
+        let conn = self.conn.lock().unwrap();
+        let _ = conn.execute("DELETE FROM responses WHERE key = ?1", [key]);
+    }
+
+    fn remove_matching(&self, matches: &dyn Fn(&str) -> bool) {
+        let conn = self.conn.lock().unwrap();
+        let keys: rusqlite::Result<Vec<String>> = conn
+            .prepare("SELECT key FROM responses")
+            .and_then(|mut stmt| stmt.query_map([], |row| row.get(0))?.collect());
+        for key in keys.unwrap_or_default() {
+            if matches(&key) {
+                let _ = conn.execute("DELETE FROM responses WHERE key = ?1", [key]);
+            }
+        }
+    }
+}
+
+fn to_unix(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn from_unix(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(body: &[u8]) -> CacheEntry {
+        CacheEntry {
+            body: body.to_vec().into(),
+            etag: Some("\"v1\"".into()),
+            last_modified: None,
+            expires_at: from_unix(2_000_000_000),
+        }
+    }
+
+    #[test]
+    fn test_sqlite_cache_round_trips_entries() {
+        let cache = SqliteCache::open_in_memory().unwrap();
+        assert!(cache.get("GET /artists/1").is_none());
+
+        cache.put("GET /artists/1", entry(b"{\"id\": 1}"));
+        let cached = cache.get("GET /artists/1").unwrap();
+        assert_eq!(&cached.body[..], b"{\"id\": 1}");
+        assert_eq!(cached.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(cached.expires_at, from_unix(2_000_000_000));
+
+        cache.remove("GET /artists/1");
+        assert!(cache.get("GET /artists/1").is_none());
+
+        cache.put("GET /artists/1", entry(b"1"));
+        cache.put("GET /artists/2", entry(b"2"));
+        cache.remove_matching(&|key| key.ends_with('1'));
+        assert!(cache.get("GET /artists/1").is_none());
+        assert!(cache.get("GET /artists/2").is_some());
+    }
+
+    #[test]
+    fn test_sqlite_cache_evicts_by_age() {
+        let cache = SqliteCache::open_in_memory()
+            .unwrap()
+            .max_age(Duration::from_secs(60));
+        cache
+            .write("old", &entry(b"old"), from_unix(1_000))
+            .unwrap();
+        cache
+            .write("new", &entry(b"new"), from_unix(1_030))
+            .unwrap();
+        assert!(cache.read("old", from_unix(1_030)).unwrap().is_some());
+        assert!(cache.read("old", from_unix(1_070)).unwrap().is_none());
+
+        cache
+            .write("newer", &entry(b"newer"), from_unix(1_070))
+            .unwrap();
+        assert!(cache.get("old").is_none());
+        assert_eq!(cache.size().unwrap(), 8);
+    }
+
+    #[test]
+    fn test_sqlite_cache_ignores_expired_rows_in_existing_database() {
+        let path = std::env::temp_dir().join(format!("discogger-{}.sqlite", std::process::id()));
+        let old = SystemTime::now() - Duration::from_secs(120);
+        SqliteCache::open(&path)
+            .unwrap()
+            .write("old", &entry(b"old"), old)
+            .unwrap();
+
+        let cache = SqliteCache::open(&path)
+            .unwrap()
+            .max_age(Duration::from_secs(60));
+        assert!(cache.get("old").is_none());
+        assert_eq!(cache.size().unwrap(), 3);
+
+        let cache = SqliteCache::open(&path).unwrap();
+        assert!(cache.get("old").is_some());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_sqlite_cache_evicts_oldest_over_max_size() {
+        let cache = SqliteCache::open_in_memory().unwrap().max_size(10);
+        cache.write("a", &entry(b"aaaa"), from_unix(1_000)).unwrap();
+        cache.write("b", &entry(b"bbbb"), from_unix(1_001)).unwrap();
+        assert_eq!(cache.size().unwrap(), 8);
+
+        cache.write("c", &entry(b"cccc"), from_unix(1_002)).unwrap();
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("b").is_some());
+        assert!(cache.get("c").is_some());
+        assert_eq!(cache.size().unwrap(), 8);
+    }
+}
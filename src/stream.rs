@@ -0,0 +1,492 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::future::BoxFuture;
+use futures_util::stream::Stream;
+use futures_util::FutureExt;
+use tokio::task::JoinHandle;
+
+use crate::client::DiscogsClient;
+use crate::error::Result;
+use crate::models::artist::ArtistRelease;
+use crate::models::collection::{CollectionItem, CollectionItemsParams};
+use crate::models::inventory::{InventoryExport, InventoryUpload};
+use crate::models::label::LabelRelease;
+use crate::models::list::ListSummary;
+use crate::models::listing::{InventoryParams, Listing};
+use crate::models::marketplace::{Order, OrderMessage, OrderParams};
+use crate::models::master::MasterVersion;
+use crate::models::release::Release;
+use crate::models::search::{SearchParams, SearchResult};
+use crate::models::user::{ContributionsParams, Submission};
+use crate::models::wantlist::Want;
+use crate::pagination::{Paginated, PaginationParams};
+
+/// How a [`PageStream`] fetches pages.
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    pub per_page: u32,
+    /// Stop after this many pages, even if more are available. With `0`
+    /// the stream is empty and nothing is fetched.
+    pub max_pages: Option<u32>,
+    /// Fetch the next page on a background task while the current one is
+    /// being consumed, rather than waiting until it runs out. Requires a
+    /// Tokio runtime.
+    pub prefetch: bool,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            per_page: 50,
+            max_pages: None,
+            prefetch: false,
+        }
+    }
+}
+
+impl StreamOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn per_page(mut self, per_page: u32) -> Self {
+        self.per_page = per_page;
+        self
+    }
+
+    pub fn max_pages(mut self, max_pages: u32) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+
+    pub fn prefetch(mut self, prefetch: bool) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+}
+
+type FetchPage<T> =
+    Box<dyn FnMut(PaginationParams) -> BoxFuture<'static, Result<Paginated<T>>> + Send>;
+
+/// A page request in flight.
+enum Pending<T> {
+    /// Polled by the stream itself.
+    Inline(BoxFuture<'static, Result<Paginated<T>>>),
+    /// Prefetched on its own task, so it progresses between polls.
+    Spawned(JoinHandle<Result<Paginated<T>>>),
+}
+
+impl<T> Pending<T> {
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Result<Paginated<T>>> {
+        match self {
+            Pending::Inline(fetch) => fetch.poll_unpin(cx),
+            Pending::Spawned(task) => task.poll_unpin(cx).map(|joined| match joined {
+                Ok(result) => result,
+                Err(e) => std::panic::resume_unwind(e.into_panic()),
+            }),
+        }
+    }
+}
+
+/// A stream of the items of a paginated endpoint, fetching pages lazily as
+/// they are needed.
+///
+/// Pages are requested through the client's rate limiter like any other
+/// request. The stream ends after the last page or the first error.
+pub struct PageStream<T> {
+    fetch: FetchPage<T>,
+    next: Option<PaginationParams>,
+    pending: Option<Pending<T>>,
+    /// A page that arrived before the previous one was used up.
+    fetched: Option<Result<Paginated<T>>>,
+    items: VecDeque<T>,
+    pages: u32,
+    max_pages: Option<u32>,
+    prefetch: bool,
+}
+
+impl<T> PageStream<T> {
+    pub(crate) fn new<F>(options: &StreamOptions, fetch: F) -> Self
+    where
+        F: FnMut(PaginationParams) -> BoxFuture<'static, Result<Paginated<T>>> + Send + 'static,
+    {
+        Self {
+            fetch: Box::new(fetch),
+            next: Some(PaginationParams::new(1, options.per_page))
+                .filter(|_| options.max_pages != Some(0)),
+            pending: None,
+            fetched: None,
+            items: VecDeque::new(),
+            pages: 0,
+            max_pages: options.max_pages,
+            prefetch: options.prefetch,
+        }
+    }
+}
+
+impl<T> Drop for PageStream<T> {
+    fn drop(&mut self) {
+        if let Some(Pending::Spawned(task)) = &self.pending {
+            task.abort();
+        }
+    }
+}
+
+// Nothing is structurally pinned; the pending request is already boxed.
+impl<T> Unpin for PageStream<T> {}
+
+impl<T: Send + 'static> Stream for PageStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.items.is_empty() {
+                match this.fetched.take() {
+                    Some(Ok(page)) => {
+                        this.pages += 1;
+                        let capped = this.max_pages.is_some_and(|max| this.pages >= max);
+                        this.next = page.next_page_params().filter(|_| !capped);
+                        this.items.extend(page.items);
+                    }
+                    Some(Err(e)) => {
+                        this.next = None;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    None => {}
+                }
+            }
+
+            if this.pending.is_none()
+                && this.fetched.is_none()
+                && (this.items.is_empty() || this.prefetch)
+            {
+                if let Some(params) = this.next.take() {
+                    let fetch = (this.fetch)(params);
+                    // A page fetched ahead runs on its own task so that it
+                    // progresses while the current one is consumed.
+                    this.pending = Some(if this.items.is_empty() {
+                        Pending::Inline(fetch)
+                    } else {
+                        Pending::Spawned(tokio::spawn(fetch))
+                    });
+                }
+            }
+
+            if let Some(pending) = this.pending.as_mut() {
+                if let Poll::Ready(result) = pending.poll(cx) {
+                    this.pending = None;
+                    this.fetched = Some(result);
+                    if this.items.is_empty() {
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(item) = this.items.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            return if this.pending.is_some() {
+                Poll::Pending
+            } else {
+                Poll::Ready(None)
+            };
+        }
+    }
+}
+
+impl DiscogsClient {
+    /// A [`PageStream`] fetching pages with `fetch`, which is given its own
+    /// handle to the client so that prefetched pages can outlive the borrow.
+    fn page_stream<T, F, Fut>(&self, options: &StreamOptions, fetch: F) -> PageStream<T>
+    where
+        F: Fn(DiscogsClient, PaginationParams) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Paginated<T>>> + Send + 'static,
+    {
+        let client = self.clone();
+        PageStream::new(options, move |page| Box::pin(fetch(client.clone(), page)))
+    }
+
+    /// Stream all of an artist's releases.
+    pub fn artist_releases_stream(
+        &self,
+        id: u64,
+        options: &StreamOptions,
+    ) -> PageStream<ArtistRelease> {
+        self.page_stream(options, move |client, page| async move {
+            client.artist_releases(id, &page).await
+        })
+    }
+
+    /// Stream all of a label's releases.
+    pub fn label_releases_stream(
+        &self,
+        id: u64,
+        options: &StreamOptions,
+    ) -> PageStream<LabelRelease> {
+        self.page_stream(options, move |client, page| async move {
+            client.label_releases(id, &page).await
+        })
+    }
+
+    /// Stream all versions of a master release.
+    pub fn master_versions_stream(
+        &self,
+        id: u64,
+        options: &StreamOptions,
+    ) -> PageStream<MasterVersion> {
+        self.page_stream(options, move |client, page| async move {
+            client.master_versions(id, &page).await
+        })
+    }
+
+    /// Stream all results of a database search.
+    pub fn search_stream(
+        &self,
+        params: &SearchParams,
+        options: &StreamOptions,
+    ) -> PageStream<SearchResult> {
+        let params = params.clone();
+        self.page_stream(options, move |client, page| {
+            let params = params.clone();
+            async move { client.search(&params, &page).await }
+        })
+    }
+
+    /// Stream all of a user's contributions.
+    pub fn contributions_stream(
+        &self,
+        username: &str,
+        params: &ContributionsParams,
+        options: &StreamOptions,
+    ) -> PageStream<Release> {
+        let username = username.to_string();
+        let params = params.clone();
+        self.page_stream(options, move |client, page| {
+            let username = username.clone();
+            let params = params.clone();
+            async move { client.contributions(&username, &params, &page).await }
+        })
+    }
+
+    /// Stream all of a user's submissions.
+    pub fn submissions_stream(
+        &self,
+        username: &str,
+        options: &StreamOptions,
+    ) -> PageStream<Submission> {
+        let username = username.to_string();
+        self.page_stream(options, move |client, page| {
+            let username = username.clone();
+            async move { client.submissions(&username, &page).await }
+        })
+    }
+
+    /// Stream all items in a collection folder.
+    pub fn collection_items_stream(
+        &self,
+        username: &str,
+        folder_id: u64,
+        params: &CollectionItemsParams,
+        options: &StreamOptions,
+    ) -> PageStream<CollectionItem> {
+        let username = username.to_string();
+        let params = params.clone();
+        self.page_stream(options, move |client, page| {
+            let username = username.clone();
+            let params = params.clone();
+            async move {
+                client
+                    .collection_items(&username, folder_id, &params, &page)
+                    .await
+            }
+        })
+    }
+
+    /// Stream all instances of a release in a user's collection.
+    pub fn collection_items_by_release_stream(
+        &self,
+        username: &str,
+        release_id: u64,
+        options: &StreamOptions,
+    ) -> PageStream<CollectionItem> {
+        let username = username.to_string();
+        self.page_stream(options, move |client, page| {
+            let username = username.clone();
+            async move {
+                client
+                    .collection_items_by_release(&username, release_id, &page)
+                    .await
+            }
+        })
+    }
+
+    /// Stream a user's whole wantlist.
+    pub fn wantlist_stream(&self, username: &str, options: &StreamOptions) -> PageStream<Want> {
+        let username = username.to_string();
+        self.page_stream(options, move |client, page| {
+            let username = username.clone();
+            async move { client.wantlist(&username, &page).await }
+        })
+    }
+
+    /// Stream all of a user's lists.
+    pub fn user_lists_stream(
+        &self,
+        username: &str,
+        options: &StreamOptions,
+    ) -> PageStream<ListSummary> {
+        let username = username.to_string();
+        self.page_stream(options, move |client, page| {
+            let username = username.clone();
+            async move { client.user_lists(&username, &page).await }
+        })
+    }
+
+    /// Stream a seller's whole inventory.
+    pub fn user_inventory_stream(
+        &self,
+        username: &str,
+        params: &InventoryParams,
+        options: &StreamOptions,
+    ) -> PageStream<Listing> {
+        let username = username.to_string();
+        let params = params.clone();
+        self.page_stream(options, move |client, page| {
+            let username = username.clone();
+            let params = params.clone();
+            async move { client.user_inventory(&username, &params, &page).await }
+        })
+    }
+
+    /// Stream all marketplace orders.
+    pub fn orders_stream(
+        &self,
+        params: &OrderParams,
+        options: &StreamOptions,
+    ) -> PageStream<Order> {
+        let params = params.clone();
+        self.page_stream(options, move |client, page| {
+            let params = params.clone();
+            async move { client.orders(&params, &page).await }
+        })
+    }
+
+    /// Stream all messages on an order.
+    pub fn order_messages_stream(
+        &self,
+        order_id: &str,
+        options: &StreamOptions,
+    ) -> PageStream<OrderMessage> {
+        let order_id = order_id.to_string();
+        self.page_stream(options, move |client, page| {
+            let order_id = order_id.clone();
+            async move { client.order_messages(&order_id, &page).await }
+        })
+    }
+
+    /// Stream all inventory exports.
+    pub fn inventory_exports_stream(&self, options: &StreamOptions) -> PageStream<InventoryExport> {
+        self.page_stream(options, move |client, page| async move {
+            client.inventory_exports(&page).await
+        })
+    }
+
+    /// Stream all inventory uploads.
+    pub fn inventory_uploads_stream(&self, options: &StreamOptions) -> PageStream<InventoryUpload> {
+        self.page_stream(options, move |client, page| async move {
+            client.inventory_uploads(&page).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::DiscogsError;
+    use crate::models::PaginationInfo;
+    use futures_util::StreamExt;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    /// A stream over `pages` pages of two numbers each, counting fetches and
+    /// failing on page `fail_on`.
+    fn numbers(
+        pages: u32,
+        fail_on: Option<u32>,
+        options: &StreamOptions,
+    ) -> (PageStream<u32>, Arc<AtomicU32>) {
+        let fetches = Arc::new(AtomicU32::new(0));
+        let counter = fetches.clone();
+        let stream = PageStream::new(options, move |params: PaginationParams| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                if Some(params.page) == fail_on {
+                    return Err(DiscogsError::RateLimited);
+                }
+                let pagination = PaginationInfo {
+                    page: params.page,
+                    pages,
+                    per_page: 2,
+                    items: pages * 2,
+                };
+                Ok(Paginated::new(
+                    vec![params.page * 10, params.page * 10 + 1],
+                    pagination,
+                ))
+            })
+        });
+        (stream, fetches)
+    }
+
+    #[tokio::test]
+    async fn test_stream_walks_all_pages() {
+        let (stream, fetches) = numbers(3, None, &StreamOptions::new());
+        let items: Vec<u32> = stream.map(|r| r.unwrap()).collect().await;
+        assert_eq!(items, vec![10, 11, 20, 21, 30, 31]);
+        assert_eq!(fetches.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stream_stops_at_max_pages() {
+        let (stream, fetches) = numbers(5, None, &StreamOptions::new().max_pages(2));
+        let items: Vec<u32> = stream.map(|r| r.unwrap()).collect().await;
+        assert_eq!(items, vec![10, 11, 20, 21]);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_with_zero_max_pages_is_empty() {
+        let (stream, fetches) = numbers(5, None, &StreamOptions::new().max_pages(0));
+        let items: Vec<u32> = stream.map(|r| r.unwrap()).collect().await;
+        assert!(items.is_empty());
+        assert_eq!(fetches.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_prefetch_requests_next_page_early() {
+        let (mut stream, fetches) = numbers(3, None, &StreamOptions::new());
+        stream.next().await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        let (mut stream, fetches) = numbers(3, None, &StreamOptions::new().prefetch(true));
+        stream.next().await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+        let items: Vec<u32> = stream.map(|r| r.unwrap()).collect().await;
+        assert_eq!(items, vec![11, 20, 21, 30, 31]);
+        assert_eq!(fetches.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_after_error() {
+        let options = StreamOptions::new().prefetch(true);
+        let (stream, _) = numbers(3, Some(2), &options);
+        let results: Vec<_> = stream.collect().await;
+        assert_eq!(results.len(), 3);
+        assert!(results[..2].iter().all(|r| r.is_ok()));
+        assert!(matches!(results[2], Err(DiscogsError::RateLimited)));
+    }
+}
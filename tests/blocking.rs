@@ -1,8 +1,8 @@
 #![cfg(feature = "blocking")]
 
 use discogger::blocking::DiscogsClient;
-use discogger::{DiscogsError, PaginationParams};
-use wiremock::matchers::{method, path};
+use discogger::{DiscogsError, PaginationParams, StreamOptions};
+use wiremock::matchers::{method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 fn json(status: u16, body: &str) -> ResponseTemplate {
@@ -133,3 +133,43 @@ async fn search_requires_auth_blocking() {
 
     assert!(matches!(err, DiscogsError::AuthRequired));
 }
+
+#[tokio::test]
+async fn artist_releases_iter_walks_every_page_blocking() {
+    let server = MockServer::start().await;
+    for (page, releases) in [
+        (1, r#"{"id": 1, "title": "One"}, {"id": 2, "title": "Two"}"#),
+        (2, r#"{"id": 3, "title": "Three"}"#),
+    ] {
+        Mock::given(method("GET"))
+            .and(path("/artists/45/releases"))
+            .and(query_param("page", page.to_string()))
+            .respond_with(json(
+                200,
+                &format!(
+                    r#"{{
+                        "pagination": {{"page": {page}, "pages": 2, "per_page": 2, "items": 3}},
+                        "releases": [{releases}]
+                    }}"#
+                ),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let base_url = server.uri();
+    let ids = run_blocking(move || {
+        let client = DiscogsClient::builder()
+            .user_agent("test/1.0")
+            .base_url(base_url)
+            .build()
+            .unwrap();
+        client
+            .artist_releases_iter(45, &StreamOptions::new().per_page(2))
+            .map(|release| release.unwrap().id)
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(ids, vec![1, 2, 3]);
+}
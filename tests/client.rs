@@ -1,5 +1,18 @@
-use discogger::{DiscogsClient, DiscogsError, PaginationParams, SearchParams, SearchType};
-use wiremock::matchers::{method, path};
+use std::sync::Arc;
+use std::time::Duration;
+
+use discogger::{
+    CachePolicy, CollectionField, CollectionItemsParams, CollectionSort, Condition,
+    ContributionSort, ContributionsParams, Currency, DiscogsClient, DiscogsError, InstanceEdit,
+    InventoryParams, InventorySort, JobStatus, ListEntity, ListingChange, ListingParams,
+    ListingStatus, MemoryCache, MessageType, Money, OrderParams, OrderStatus, OrderUpdate,
+    PaginationParams, PollOptions, ProfileEdit, RetryPolicy, SearchParams, SearchType, SortOrder,
+    StreamOptions, Submission, UploadListing, WantEdit,
+};
+use futures_util::StreamExt;
+use wiremock::matchers::{
+    body_json, body_string, body_string_contains, header, header_regex, method, path, query_param,
+};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 fn client(base_url: &str) -> DiscogsClient {
@@ -19,6 +32,15 @@ fn auth_client(base_url: &str) -> DiscogsClient {
         .unwrap()
 }
 
+fn oauth_client(base_url: &str) -> DiscogsClient {
+    DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .oauth("ckey", "csecret", "token", "tsecret")
+        .base_url(base_url)
+        .build()
+        .unwrap()
+}
+
 fn json(status: u16, body: &str) -> ResponseTemplate {
     ResponseTemplate::new(status)
         .insert_header("content-type", "application/json")
@@ -86,6 +108,105 @@ async fn release_parses_response() {
     assert_eq!(release.title.as_deref(), Some("Never Gonna Give You Up"));
 }
 
+#[tokio::test]
+async fn release_with_currency_tags_lowest_price() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/releases/249504"))
+        .and(query_param("curr_abbr", "GBP"))
+        .respond_with(json(
+            200,
+            r#"{"id": 249504, "num_for_sale": 58, "lowest_price": 0.63}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let release = client(&server.uri())
+        .release_with_currency(249504, Currency::Gbp)
+        .await
+        .unwrap();
+    assert_eq!(release.lowest_price, Some(Money::new(0.63, Currency::Gbp)));
+}
+
+#[tokio::test]
+async fn release_stats_batch_keys_results_by_release() {
+    let server = MockServer::start().await;
+    for (id, have) in [(1, 10), (2, 20)] {
+        Mock::given(method("GET"))
+            .and(path(format!("/releases/{id}/stats")))
+            .respond_with(json(
+                200,
+                &format!(r#"{{"num_have": {have}, "num_want": 5}}"#),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/releases/3/stats"))
+        .respond_with(json(404, r#"{"message": "Release not found."}"#))
+        .mount(&server)
+        .await;
+
+    let stats = client(&server.uri())
+        .release_stats_batch(&[2, 1, 3, 1])
+        .await;
+    assert_eq!(stats.len(), 3);
+    assert_eq!(stats[&1].as_ref().unwrap().num_have, 10);
+    assert_eq!(stats[&2].as_ref().unwrap().num_have, 20);
+    assert!(matches!(
+        stats[&3],
+        Err(DiscogsError::Api { status: 404, .. })
+    ));
+}
+
+#[tokio::test]
+async fn release_rating_parses_community_average() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/releases/249504/rating"))
+        .respond_with(json(
+            200,
+            r#"{"rating": {"count": 45, "average": 3.42}, "release_id": 249504}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let rating = client(&server.uri()).release_rating(249504).await.unwrap();
+    assert_eq!(rating.rating.count, 45);
+    assert_eq!(rating.rating.average, 3.42);
+}
+
+#[tokio::test]
+async fn put_user_release_rating_sends_rating() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/releases/249504/rating/memory"))
+        .and(body_json(serde_json::json!({"rating": 4})))
+        .respond_with(json(
+            201,
+            r#"{"username": "memory", "release_id": 249504, "rating": 4}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let rating = auth_client(&server.uri())
+        .put_user_release_rating(249504, "memory", 4)
+        .await
+        .unwrap();
+    assert_eq!(rating.rating, 4);
+}
+
+#[tokio::test]
+async fn put_user_release_rating_rejects_out_of_range() {
+    let err = auth_client("http://localhost")
+        .put_user_release_rating(249504, "memory", 0)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::Configuration(_)));
+}
+
 // --- label ---
 
 #[tokio::test]
@@ -214,105 +335,1707 @@ async fn search_parses_response() {
     assert_eq!(page.items[0].result_type.as_deref(), Some("artist"));
 }
 
-// --- error handling ---
+// --- user identity / profile ---
 
 #[tokio::test]
-async fn rate_limited_response_returns_error() {
+async fn identity_requires_auth() {
+    let server = MockServer::start().await;
+    let err = client(&server.uri()).identity().await.unwrap_err();
+    assert!(matches!(err, DiscogsError::AuthRequired));
+}
+
+#[tokio::test]
+async fn identity_parses_response() {
     let server = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/artists/1"))
-        .respond_with(ResponseTemplate::new(429))
+        .and(path("/oauth/identity"))
+        .respond_with(json(
+            200,
+            r#"{"id": 1, "username": "example", "consumer_name": "Test App"}"#,
+        ))
         .mount(&server)
         .await;
 
-    let err = client(&server.uri()).artist(1).await.unwrap_err();
-    assert!(matches!(err, DiscogsError::RateLimited));
+    let identity = auth_client(&server.uri()).identity().await.unwrap();
+    assert_eq!(identity.username, "example");
+    assert_eq!(identity.consumer_name.as_deref(), Some("Test App"));
 }
 
 #[tokio::test]
-async fn api_error_response_captures_status_and_body() {
+async fn user_profile_parses_response() {
     let server = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/artists/999"))
-        .respond_with(json(404, r#"{"message": "Release not found."}"#))
+        .and(path("/users/example"))
+        .respond_with(json(
+            200,
+            r#"{"id": 1, "username": "example", "num_wantlist": 3}"#,
+        ))
         .mount(&server)
         .await;
 
-    let err = client(&server.uri()).artist(999).await.unwrap_err();
-    match err {
-        DiscogsError::Api { status, body } => {
-            assert_eq!(status, 404);
-            assert!(body.contains("not found"));
-        }
-        other => panic!("expected Api error, got {other:?}"),
-    }
+    let profile = client(&server.uri()).user_profile("example").await.unwrap();
+    assert_eq!(profile.id, 1);
+    assert_eq!(profile.num_wantlist, Some(3));
 }
 
-// --- download_image / release_cover_art ---
+#[tokio::test]
+async fn edit_user_profile_posts_changes() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/users/example"))
+        .and(body_json(serde_json::json!({"location": "Berlin"})))
+        .respond_with(json(
+            200,
+            r#"{"id": 1, "username": "example", "location": "Berlin"}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let profile = auth_client(&server.uri())
+        .edit_user_profile("example", &ProfileEdit::new().location("Berlin"))
+        .await
+        .unwrap();
+    assert_eq!(profile.location.as_deref(), Some("Berlin"));
+}
 
 #[tokio::test]
-async fn download_image_returns_bytes() {
+async fn contributions_sends_sort() {
     let server = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/images/cover.jpg"))
-        .respond_with(
-            ResponseTemplate::new(200)
-                .insert_header("content-type", "image/jpeg")
-                .set_body_bytes(b"FAKEJPEGDATA".to_vec()),
-        )
+        .and(path("/users/memory/contributions"))
+        .and(query_param("sort", "year"))
+        .and(query_param("sort_order", "desc"))
+        .respond_with(json(
+            200,
+            r#"{
+                "pagination": {"page": 1, "pages": 1, "per_page": 50, "items": 1},
+                "contributions": [{"id": 249504, "title": "Never Gonna Give You Up"}]
+            }"#,
+        ))
         .mount(&server)
         .await;
 
-    let url = format!("{}/images/cover.jpg", server.uri());
-    let bytes = client(&server.uri()).download_image(&url).await.unwrap();
-    assert_eq!(&bytes[..], b"FAKEJPEGDATA");
+    let params = ContributionsParams::new()
+        .sort(ContributionSort::Year)
+        .sort_order(SortOrder::Desc);
+    let page = client(&server.uri())
+        .contributions("memory", &params, &PaginationParams::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items[0].id, 249504);
 }
 
 #[tokio::test]
-async fn release_cover_art_returns_none_when_no_images() {
+async fn submissions_flattens_grouped_entities() {
     let server = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/releases/1"))
-        .respond_with(json(200, r#"{"id": 1, "title": "No Cover", "images": []}"#))
+        .and(path("/users/memory/submissions"))
+        .respond_with(json(
+            200,
+            r#"{
+                "pagination": {"page": 1, "pages": 2, "per_page": 50, "items": 53},
+                "submissions": {
+                    "artists": [{"id": 1, "name": "The Persuader"}],
+                    "labels": [{"id": 895, "name": "RCA"}],
+                    "releases": [{"id": 249504}]
+                }
+            }"#,
+        ))
         .mount(&server)
         .await;
 
-    let result = client(&server.uri()).release_cover_art(1).await.unwrap();
-    assert!(result.is_none());
+    let page = client(&server.uri())
+        .submissions("memory", &PaginationParams::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 3);
+    assert!(matches!(page.items[1], Submission::Label(ref l) if l.id == 895));
+    assert!(matches!(page.items[2], Submission::Release(ref r) if r.id == 249504));
+    assert!(page.has_next());
 }
 
+// --- collection ---
+
 #[tokio::test]
-async fn release_cover_art_downloads_primary_image() {
+async fn collection_folders_parses_response() {
     let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/example/collection/folders"))
+        .respond_with(json(
+            200,
+            r#"{"folders": [
+                {"id": 0, "name": "All", "count": 23},
+                {"id": 1, "name": "Uncategorized", "count": 20}
+            ]}"#,
+        ))
+        .mount(&server)
+        .await;
 
-    let release_body = format!(
-        r#"{{"id": 1, "title": "Has Cover", "images": [{{"type": "primary", "uri": "{}/images/cover.jpg", "width": 300, "height": 300}}]}}"#,
-        server.uri()
-    );
+    let folders = client(&server.uri())
+        .collection_folders("example")
+        .await
+        .unwrap();
+    assert_eq!(folders.len(), 2);
+    assert_eq!(folders[1].name, "Uncategorized");
+}
 
-    Mock::given(method("GET"))
-        .and(path("/releases/1"))
-        .respond_with(json(200, &release_body))
+#[tokio::test]
+async fn create_collection_folder_requires_auth() {
+    let server = MockServer::start().await;
+    let err = client(&server.uri())
+        .create_collection_folder("example", "Jazz")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::AuthRequired));
+}
+
+#[tokio::test]
+async fn create_collection_folder_posts_name() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/users/example/collection/folders"))
+        .and(body_json(serde_json::json!({"name": "Jazz"})))
+        .respond_with(json(201, r#"{"id": 3, "name": "Jazz", "count": 0}"#))
+        .mount(&server)
+        .await;
+
+    let folder = auth_client(&server.uri())
+        .create_collection_folder("example", "Jazz")
+        .await
+        .unwrap();
+    assert_eq!(folder.id, 3);
+}
+
+#[tokio::test]
+async fn delete_collection_folder_accepts_no_content() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/users/example/collection/folders/3"))
+        .respond_with(ResponseTemplate::new(204))
         .mount(&server)
         .await;
 
+    auth_client(&server.uri())
+        .delete_collection_folder("example", 3)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn collection_items_sends_sort_and_parses_page() {
+    let server = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/images/cover.jpg"))
-        .respond_with(
-            ResponseTemplate::new(200)
-                .insert_header("content-type", "image/jpeg")
-                .set_body_bytes(b"JPEGDATA".to_vec()),
+        .and(path("/users/example/collection/folders/0/releases"))
+        .and(query_param("sort", "year"))
+        .and(query_param("sort_order", "asc"))
+        .respond_with(json(
+            200,
+            r#"{
+                "pagination": {"page": 1, "pages": 1, "per_page": 50, "items": 1},
+                "releases": [{
+                    "id": 2464521,
+                    "instance_id": 7,
+                    "rating": 5,
+                    "basic_information": {"id": 2464521, "title": "Year Zero"}
+                }]
+            }"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let params = CollectionItemsParams::new()
+        .sort(CollectionSort::Year)
+        .sort_order(SortOrder::Asc);
+    let page = client(&server.uri())
+        .collection_items("example", 0, &params, &PaginationParams::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].instance_id, 7);
+}
+
+#[tokio::test]
+async fn add_to_collection_returns_instance() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/users/example/collection/folders/1/releases/130076"))
+        .respond_with(json(201, r#"{"instance_id": 3, "resource_url": "x"}"#))
+        .mount(&server)
+        .await;
+
+    let instance = auth_client(&server.uri())
+        .add_to_collection("example", 1, 130076)
+        .await
+        .unwrap();
+    assert_eq!(instance.instance_id, 3);
+}
+
+#[tokio::test]
+async fn add_to_all_folder_is_rejected() {
+    let server = MockServer::start().await;
+    let err = auth_client(&server.uri())
+        .add_to_collection("example", 0, 130076)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::Configuration(_)));
+}
+
+#[tokio::test]
+async fn edit_collection_instance_sends_signed_form() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(
+            "/users/example/collection/folders/1/releases/130076/instances/3",
+        ))
+        .and(body_string("rating=5&folder_id=4"))
+        .and(header_regex(
+            "Authorization",
+            r#"^OAuth .*oauth_signature="#,
+        ))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    oauth_client(&server.uri())
+        .edit_collection_instance(
+            "example",
+            1,
+            130076,
+            3,
+            &InstanceEdit::new().rating(5).folder_id(4),
         )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn delete_collection_instance_sends_delete() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path(
+            "/users/example/collection/folders/1/releases/130076/instances/3",
+        ))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
         .mount(&server)
         .await;
 
-    let art = client(&server.uri())
-        .release_cover_art(1)
+    auth_client(&server.uri())
+        .delete_collection_instance("example", 1, 130076, 3)
         .await
-        .unwrap()
         .unwrap();
+}
 
-    assert_eq!(&art.data[..], b"JPEGDATA");
-    assert_eq!(art.width, Some(300));
-    assert_eq!(art.height, Some(300));
+#[tokio::test]
+async fn collection_fields_parses_variants() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/example/collection/fields"))
+        .respond_with(json(
+            200,
+            r#"{"fields": [
+                {"id": 1, "name": "Media", "type": "dropdown", "options": ["Mint (M)"]},
+                {"id": 3, "name": "Notes", "type": "textarea", "lines": 3}
+            ]}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let fields = client(&server.uri())
+        .collection_fields("example")
+        .await
+        .unwrap();
+    assert!(matches!(fields[0], CollectionField::Dropdown { id: 1, .. }));
+    assert!(matches!(
+        fields[1],
+        CollectionField::Textarea { lines: 3, .. }
+    ));
+}
+
+#[tokio::test]
+async fn edit_instance_field_sends_value() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(
+            "/users/example/collection/folders/1/releases/130076/instances/3/fields/8",
+        ))
+        .and(query_param("value", "Near Mint (NM or M-)"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    auth_client(&server.uri())
+        .edit_instance_field("example", 1, 130076, 3, 8, "Near Mint (NM or M-)")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn collection_value_parses_formatted_amounts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/example/collection/value"))
+        .respond_with(json(
+            200,
+            r#"{"maximum": "€1.234,56", "median": "€650,00", "minimum": "€98,10"}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let value = auth_client(&server.uri())
+        .collection_value("example")
+        .await
+        .unwrap();
+    assert_eq!(value.maximum, Money::new(1234.56, Currency::Eur));
+    assert_eq!(value.median, Money::new(650.0, Currency::Eur));
+}
+
+// --- wantlist ---
+
+#[tokio::test]
+async fn wantlist_parses_paginated_response() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/example/wants"))
+        .respond_with(json(
+            200,
+            r#"{
+                "pagination": {"page": 1, "pages": 3, "per_page": 50, "items": 120},
+                "wants": [{"id": 1867708, "rating": 4, "basic_information": {"id": 1867708}}]
+            }"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let page = client(&server.uri())
+        .wantlist("example", &PaginationParams::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items[0].id, 1867708);
+    assert!(page.has_next());
+}
+
+#[tokio::test]
+async fn add_want_puts_notes_and_rating() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/users/example/wants/1867708"))
+        .and(query_param("notes", "first pressing"))
+        .and(query_param("rating", "5"))
+        .respond_with(json(
+            201,
+            r#"{"id": 1867708, "rating": 5, "notes": "first pressing", "basic_information": {"id": 1867708}}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let want = auth_client(&server.uri())
+        .add_want(
+            "example",
+            1867708,
+            &WantEdit::new().notes("first pressing").rating(5),
+        )
+        .await
+        .unwrap();
+    assert_eq!(want.rating, 5);
+}
+
+#[tokio::test]
+async fn delete_want_requires_auth() {
+    let server = MockServer::start().await;
+    let err = client(&server.uri())
+        .delete_want("example", 1867708)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::AuthRequired));
+}
+
+// --- lists ---
+
+#[tokio::test]
+async fn user_lists_parses_paginated_response() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/memory/lists"))
+        .respond_with(json(
+            200,
+            r#"{
+                "pagination": {"page": 1, "pages": 1, "per_page": 50, "items": 1},
+                "lists": [{"id": 21, "name": "Best Of Discogs", "public": true}]
+            }"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let page = client(&server.uri())
+        .user_lists("memory", &PaginationParams::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items[0].name, "Best Of Discogs");
+}
+
+#[tokio::test]
+async fn list_parses_typed_items() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/lists/21"))
+        .respond_with(json(
+            200,
+            r#"{
+                "list_id": 21,
+                "name": "Best Of Discogs",
+                "items": [
+                    {"type": "release", "id": 249504, "comment": "A classic"},
+                    {"type": "label", "id": 895}
+                ]
+            }"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let list = client(&server.uri()).list(21).await.unwrap();
+    assert_eq!(list.items[0].entity, ListEntity::Release { id: 249504 });
+    assert_eq!(list.items[1].entity, ListEntity::Label { id: 895 });
+}
+
+// --- marketplace listings ---
+
+#[tokio::test]
+async fn user_inventory_sends_filters_and_parses_listings() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/rappcats/inventory"))
+        .and(query_param("status", "For Sale"))
+        .and(query_param("sort", "price"))
+        .respond_with(json(
+            200,
+            r#"{
+                "pagination": {"page": 1, "pages": 1, "per_page": 50, "items": 1},
+                "listings": [{
+                    "id": 150899904,
+                    "status": "For Sale",
+                    "price": {"currency": "EUR", "value": 20.0},
+                    "condition": "Very Good (VG)",
+                    "seller": {"id": 1, "username": "rappcats"},
+                    "release": {"id": 5610049, "description": "LAMB - Fear Of Fours"}
+                }]
+            }"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let params = InventoryParams::new()
+        .status(ListingStatus::ForSale)
+        .sort(InventorySort::Price);
+    let page = client(&server.uri())
+        .user_inventory("rappcats", &params, &PaginationParams::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items[0].price, Money::new(20.0, Currency::Eur));
+    assert_eq!(page.items[0].condition, Condition::VeryGood);
+}
+
+#[tokio::test]
+async fn create_listing_posts_params() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/marketplace/listings"))
+        .and(body_json(serde_json::json!({
+            "release_id": 249504,
+            "condition": "Very Good Plus (VG+)",
+            "sleeve_condition": "Generic",
+            "price": 12.5,
+            "status": "For Sale",
+            "allow_offers": true
+        })))
+        .respond_with(json(
+            201,
+            r#"{"listing_id": 41578241, "resource_url": "x"}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let params = ListingParams::new(249504, Condition::VeryGoodPlus, 12.5)
+        .sleeve_condition(Condition::Generic)
+        .allow_offers(true);
+    let created = auth_client(&server.uri())
+        .create_listing(&params)
+        .await
+        .unwrap();
+    assert_eq!(created.listing_id, 41578241);
+}
+
+#[tokio::test]
+async fn create_listing_validates_before_sending() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(422))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let params = ListingParams::new(249504, Condition::NoCover, 12.5);
+    let err = auth_client(&server.uri())
+        .create_listing(&params)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::Configuration(_)));
+}
+
+#[tokio::test]
+async fn delete_listing_sends_delete() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/marketplace/listings/41578241"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    auth_client(&server.uri())
+        .delete_listing(41578241)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn marketplace_stats_parses_blocked_flag() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/marketplace/stats/249504"))
+        .respond_with(json(
+            200,
+            r#"{"lowest_price": null, "num_for_sale": 0, "blocked_from_sale": true}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let stats = client(&server.uri())
+        .marketplace_stats(249504)
+        .await
+        .unwrap();
+    assert!(stats.blocked_from_sale);
+    assert!(stats.lowest_price.is_none());
+}
+
+#[tokio::test]
+async fn marketplace_fee_formats_price_in_path() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/marketplace/fee/10.00/EUR"))
+        .respond_with(json(200, r#"{"value": 0.8, "currency": "EUR"}"#))
+        .mount(&server)
+        .await;
+
+    let fee = client(&server.uri())
+        .marketplace_fee(10.0, Currency::Eur)
+        .await
+        .unwrap();
+    assert_eq!(fee, Money::new(0.8, Currency::Eur));
+}
+
+#[tokio::test]
+async fn price_suggestions_keys_by_condition() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/marketplace/price_suggestions/10"))
+        .respond_with(json(
+            200,
+            r#"{
+                "Mint (M)": {"currency": "USD", "value": 14.1},
+                "Very Good Plus (VG+)": {"currency": "USD", "value": 9.5},
+                "Poor (P)": {"currency": "USD", "value": 1.1}
+            }"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let suggestions = auth_client(&server.uri())
+        .price_suggestions(10)
+        .await
+        .unwrap();
+    assert_eq!(suggestions.len(), 3);
+    assert_eq!(
+        suggestions[&Condition::VeryGoodPlus],
+        Money::new(9.5, Currency::Usd)
+    );
+    assert_eq!(suggestions.keys().next(), Some(&Condition::Mint));
+}
+
+#[tokio::test]
+async fn price_suggestions_leaves_out_unknown_grades() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/marketplace/price_suggestions/10"))
+        .respond_with(json(
+            200,
+            r#"{
+                "Mint (M)": {"currency": "USD", "value": 14.1},
+                "Very Good Minus (VG-)": {"currency": "USD", "value": 6.0},
+                "Excellent (EX)": {"currency": "USD", "value": 11.0}
+            }"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let suggestions = auth_client(&server.uri())
+        .price_suggestions(10)
+        .await
+        .unwrap();
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(
+        suggestions[&Condition::Mint],
+        Money::new(14.1, Currency::Usd)
+    );
+    assert!(!suggestions.contains_key(&Condition::Other));
+}
+
+// --- inventory export ---
+
+const EXPORT_CSV: &str = "\
+listing_id,artist,title,label,catno,format,release_id,status,price,listed,comments,media_condition,sleeve_condition,accept_offer,external_id,weight,format_quantity,flat_shipping,location
+123,Rick Astley,Never Gonna Give You Up,RCA,PB 49801,Vinyl,249504,For Sale,12.50,,,Very Good Plus (VG+),Generic,N,,,auto,,
+";
+
+fn fast_poll() -> PollOptions {
+    PollOptions::new()
+        .initial_interval(Duration::from_millis(5))
+        .max_interval(Duration::from_millis(10))
+        .timeout(Duration::from_secs(5))
+}
+
+async fn mount_export_request(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/inventory/export"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Location", format!("{}/inventory/export/42", server.uri())),
+        )
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn export_inventory_polls_then_parses_csv() {
+    let server = MockServer::start().await;
+    mount_export_request(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/export/42"))
+        .respond_with(json(200, r#"{"id": 42, "status": "pending"}"#))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/export/42"))
+        .respond_with(json(200, r#"{"id": 42, "status": "success"}"#))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/export/42/download"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/csv")
+                .set_body_string(EXPORT_CSV),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let rows = auth_client(&server.uri())
+        .export_inventory(&fast_poll())
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].listing_id, 123);
+    assert_eq!(rows[0].media_condition, Condition::VeryGoodPlus);
+    assert_eq!(rows[0].price, 12.5);
+}
+
+#[tokio::test]
+async fn export_inventory_reports_failed_job() {
+    let server = MockServer::start().await;
+    mount_export_request(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/export/42"))
+        .respond_with(json(200, r#"{"id": 42, "status": "failure"}"#))
+        .mount(&server)
+        .await;
+
+    let err = auth_client(&server.uri())
+        .export_inventory(&fast_poll())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::JobFailed { id: 42 }));
+}
+
+#[tokio::test]
+async fn export_inventory_waits_without_deadline() {
+    let server = MockServer::start().await;
+    mount_export_request(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/export/42"))
+        .respond_with(json(200, r#"{"id": 42, "status": "pending"}"#))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/export/42"))
+        .respond_with(json(200, r#"{"id": 42, "status": "success"}"#))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/export/42/download"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(EXPORT_CSV))
+        .mount(&server)
+        .await;
+
+    let rows = auth_client(&server.uri())
+        .export_inventory(&fast_poll().timeout(Duration::MAX))
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[tokio::test]
+async fn export_inventory_times_out() {
+    let server = MockServer::start().await;
+    mount_export_request(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/export/42"))
+        .respond_with(json(200, r#"{"id": 42, "status": "pending"}"#))
+        .mount(&server)
+        .await;
+
+    let err = auth_client(&server.uri())
+        .export_inventory(&fast_poll().timeout(Duration::from_millis(30)))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::JobTimeout { id: 42 }));
+}
+
+#[tokio::test]
+async fn inventory_exports_parses_items_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/export"))
+        .respond_with(json(
+            200,
+            r#"{
+                "pagination": {"page": 1, "pages": 1, "per_page": 50, "items": 1},
+                "items": [{"id": 42, "status": "success", "filename": "inventory.csv"}]
+            }"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let page = auth_client(&server.uri())
+        .inventory_exports(&PaginationParams::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items[0].status, JobStatus::Success);
+}
+
+// --- inventory upload ---
+
+#[tokio::test]
+async fn upload_inventory_add_sends_multipart_csv() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/inventory/upload/add"))
+        .and(header_regex(
+            "content-type",
+            "^multipart/form-data; boundary=",
+        ))
+        .and(body_string_contains(
+            r#"name="upload"; filename="inventory.csv""#,
+        ))
+        .and(body_string_contains(
+            "release_id,price,media_condition\n249504,12.5,Very Good Plus (VG+)\n",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Location", format!("{}/inventory/upload/7", server.uri())),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let listings = [UploadListing::new(249504, Condition::VeryGoodPlus, 12.5)];
+    let upload_id = auth_client(&server.uri())
+        .upload_inventory_add(&listings)
+        .await
+        .unwrap();
+    assert_eq!(upload_id, 7);
+}
+
+#[tokio::test]
+async fn upload_inventory_change_then_wait_for_results() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/inventory/upload/change"))
+        .and(body_string_contains("listing_id,price\n123,15\n"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Location", format!("{}/inventory/upload/8", server.uri())),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/upload/8"))
+        .respond_with(json(
+            200,
+            r#"{"id": 8, "status": "pending", "type": "change"}"#,
+        ))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/inventory/upload/8"))
+        .respond_with(json(
+            200,
+            r#"{"id": 8, "status": "success", "type": "change", "results": "CSV file contains 1 records. Processed 1 records."}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let client = auth_client(&server.uri());
+    let upload_ids = client
+        .upload_inventory_change(&[ListingChange::new(123).price(15.0)])
+        .await
+        .unwrap();
+    assert_eq!(upload_ids, vec![8]);
+    let upload = client
+        .wait_for_inventory_upload(upload_ids[0], &fast_poll())
+        .await
+        .unwrap();
+    assert!(upload.results.unwrap().contains("Processed 1 records"));
+}
+
+#[tokio::test]
+async fn upload_inventory_change_splits_rows_by_changed_fields() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/inventory/upload/change"))
+        .and(body_string_contains("listing_id,price\n123,15\n125,20\n"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Location", format!("{}/inventory/upload/8", server.uri())),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/inventory/upload/change"))
+        .and(body_string_contains("listing_id,location\n124,Shelf B\n"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Location", format!("{}/inventory/upload/9", server.uri())),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let upload_ids = auth_client(&server.uri())
+        .upload_inventory_change(&[
+            ListingChange::new(123).price(15.0),
+            ListingChange::new(124).location("Shelf B"),
+            ListingChange::new(125).price(20.0),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(upload_ids, vec![8, 9]);
+}
+
+#[tokio::test]
+async fn upload_inventory_change_reports_accepted_uploads_on_failure() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/inventory/upload/change"))
+        .and(body_string_contains("listing_id,price\n"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Location", format!("{}/inventory/upload/8", server.uri())),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/inventory/upload/change"))
+        .and(body_string_contains("listing_id,location\n"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let err = auth_client(&server.uri())
+        .upload_inventory_change(&[
+            ListingChange::new(123).price(15.0),
+            ListingChange::new(124).location("Shelf B"),
+        ])
+        .await
+        .unwrap_err();
+    let DiscogsError::PartialUpload { upload_ids, source } = err else {
+        panic!("expected a partial upload, got {err:?}");
+    };
+    assert_eq!(upload_ids, vec![8]);
+    assert!(matches!(*source, DiscogsError::Api { status: 500, .. }));
+}
+
+#[tokio::test]
+async fn upload_inventory_delete_rejects_empty_batch() {
+    let err = auth_client("http://localhost")
+        .upload_inventory_delete(&[])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::Configuration(_)));
+}
+
+// --- orders ---
+
+const ORDER: &str = r#"{
+    "id": "1-1",
+    "status": "Payment Received",
+    "next_status": ["In Progress", "Shipped", "Refund Sent"],
+    "items": [{"id": 41578242, "release": {"id": 1}, "price": {"currency": "USD", "value": 42.0}}],
+    "seller": {"id": 1, "username": "example_seller"},
+    "buyer": {"id": 2, "username": "example_buyer"}
+}"#;
+
+#[tokio::test]
+async fn orders_sends_filters_and_parses_page() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/marketplace/orders"))
+        .and(query_param("status", "Payment Received"))
+        .and(query_param("archived", "false"))
+        .respond_with(json(
+            200,
+            &format!(
+                r#"{{"pagination": {{"page": 1, "pages": 1, "per_page": 50, "items": 1}}, "orders": [{ORDER}]}}"#
+            ),
+        ))
+        .mount(&server)
+        .await;
+
+    let params = OrderParams::new()
+        .status(OrderStatus::PaymentReceived)
+        .archived(false);
+    let page = auth_client(&server.uri())
+        .orders(&params, &PaginationParams::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items[0].id, "1-1");
+    assert_eq!(
+        page.items[0].items[0].price,
+        Money::new(42.0, Currency::Usd)
+    );
+}
+
+#[tokio::test]
+async fn orders_requires_auth() {
+    let err = client("http://localhost")
+        .orders(&OrderParams::new(), &PaginationParams::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::AuthRequired));
+}
+
+#[tokio::test]
+async fn edit_order_posts_update() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/marketplace/orders/1-1"))
+        .respond_with(json(200, ORDER))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/marketplace/orders/1-1"))
+        .and(body_json(serde_json::json!({"status": "Shipped"})))
+        .respond_with(json(
+            200,
+            &ORDER.replace(r#""status": "Payment Received""#, r#""status": "Shipped""#),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = auth_client(&server.uri());
+    let order = client.order("1-1").await.unwrap();
+    let updated = client
+        .edit_order(&order, &OrderUpdate::new().status(OrderStatus::Shipped))
+        .await
+        .unwrap();
+    assert_eq!(updated.status, OrderStatus::Shipped);
+}
+
+#[tokio::test]
+async fn edit_order_rejects_illegal_transition() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/marketplace/orders/1-1"))
+        .respond_with(json(200, ORDER))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(json(200, ORDER))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = auth_client(&server.uri());
+    let order = client.order("1-1").await.unwrap();
+    let err = client
+        .edit_order(&order, &OrderUpdate::new().status(OrderStatus::NewOrder))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::Configuration(_)));
+}
+
+#[tokio::test]
+async fn order_messages_parses_thread() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/marketplace/orders/1-1/messages"))
+        .respond_with(json(
+            200,
+            r#"{
+                "pagination": {"page": 1, "pages": 1, "per_page": 50, "items": 2},
+                "messages": [
+                    {"type": "message", "message": "Hello", "from": {"id": 2, "username": "example_buyer"}},
+                    {"type": "status", "message": "Status changed to Shipped"}
+                ]
+            }"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let page = auth_client(&server.uri())
+        .order_messages("1-1", &PaginationParams::default())
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.items[1].message_type, MessageType::Status);
+}
+
+#[tokio::test]
+async fn post_order_message_sends_message_and_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/marketplace/orders/1-1/messages"))
+        .and(body_json(
+            serde_json::json!({"message": "Shipped today", "status": "Shipped"}),
+        ))
+        .respond_with(json(
+            201,
+            r#"{"type": "message", "message": "Shipped today"}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let message = auth_client(&server.uri())
+        .post_order_message("1-1", Some("Shipped today"), Some(OrderStatus::Shipped))
+        .await
+        .unwrap();
+    assert_eq!(message.message.as_deref(), Some("Shipped today"));
+}
+
+// --- streams ---
+
+#[tokio::test]
+async fn label_releases_stream_walks_every_page() {
+    let server = MockServer::start().await;
+    for page in 1..=2 {
+        Mock::given(method("GET"))
+            .and(path("/labels/1/releases"))
+            .and(query_param("page", page.to_string()))
+            .and(query_param("per_page", "2"))
+            .respond_with(json(
+                200,
+                &format!(
+                    r#"{{
+                        "pagination": {{"page": {page}, "pages": 2, "per_page": 2, "items": 3}},
+                        "releases": [{}]
+                    }}"#,
+                    if page == 1 {
+                        r#"{"id": 1, "title": "One"}, {"id": 2, "title": "Two"}"#
+                    } else {
+                        r#"{"id": 3, "title": "Three"}"#
+                    }
+                ),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = client(&server.uri());
+    let options = StreamOptions::new().per_page(2).prefetch(true);
+    let ids: Vec<u64> = client
+        .label_releases_stream(1, &options)
+        .map(|release| release.unwrap().id)
+        .collect()
+        .await;
+    assert_eq!(ids, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn stream_prefetches_next_page_while_consumer_is_busy() {
+    let server = MockServer::start().await;
+    for page in 1..=2 {
+        Mock::given(method("GET"))
+            .and(path("/labels/1/releases"))
+            .and(query_param("page", page.to_string()))
+            .respond_with(
+                json(
+                    200,
+                    &format!(
+                        r#"{{
+                            "pagination": {{"page": {page}, "pages": 2, "per_page": 1, "items": 2}},
+                            "releases": [{{"id": {page}, "title": "Release {page}"}}]
+                        }}"#
+                    ),
+                )
+                // Out of requests, so page 2 waits ~100ms for the rate limiter.
+                .insert_header("X-Discogs-Ratelimit", "600")
+                .insert_header("X-Discogs-Ratelimit-Used", "600"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = client(&server.uri());
+    let options = StreamOptions::new().per_page(1).prefetch(true);
+    let mut stream = client.label_releases_stream(1, &options);
+    assert_eq!(stream.next().await.unwrap().unwrap().id, 1);
+
+    // Work on the first item without polling the stream.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].url.query(), Some("page=2&per_page=1"));
+
+    assert_eq!(stream.next().await.unwrap().unwrap().id, 2);
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn search_stream_yields_auth_error_and_ends() {
+    let server = MockServer::start().await;
+    let client = client(&server.uri());
+    let params = SearchParams::new().query("aphex twin");
+    let results: Vec<_> = client
+        .search_stream(&params, &StreamOptions::new())
+        .collect()
+        .await;
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results[0], Err(DiscogsError::AuthRequired)));
+}
+
+// --- cache ---
+
+fn cached_client(base_url: &str, cache: Arc<MemoryCache>) -> DiscogsClient {
+    DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .base_url(base_url)
+        .cache(cache)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn fresh_cache_entry_skips_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artists/1"))
+        .respond_with(json(200, r#"{"id": 1, "name": "Aphex Twin"}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let cache = Arc::new(MemoryCache::new(10));
+    let client = cached_client(&server.uri(), cache.clone());
+    client.artist(1).await.unwrap();
+    let artist = client.artist(1).await.unwrap();
+    assert_eq!(artist.name, "Aphex Twin");
+    assert_eq!(cache.len(), 1);
+}
+
+#[tokio::test]
+async fn cache_forever_ttl_keeps_entry_fresh() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artists/1"))
+        .respond_with(json(200, r#"{"id": 1, "name": "Aphex Twin"}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .base_url(server.uri())
+        .cache(MemoryCache::new(10))
+        .cache_policy(CachePolicy::new().ttl("/artists", Duration::MAX))
+        .build()
+        .unwrap();
+    client.artist(1).await.unwrap();
+    let artist = client.artist(1).await.unwrap();
+    assert_eq!(artist.name, "Aphex Twin");
+}
+
+#[tokio::test]
+async fn stale_cache_entry_is_revalidated_with_etag() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/rodneyfool"))
+        .and(header("If-None-Match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/users/rodneyfool"))
+        .respond_with(
+            json(200, r#"{"id": 1, "username": "rodneyfool"}"#).insert_header("ETag", "\"v1\""),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = cached_client(&server.uri(), Arc::new(MemoryCache::new(10)));
+    client.user_profile("rodneyfool").await.unwrap();
+    let profile = client.user_profile("rodneyfool").await.unwrap();
+    assert_eq!(profile.username, "rodneyfool");
+}
+
+#[tokio::test]
+async fn cache_is_keyed_by_credentials() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artists/1"))
+        .respond_with(json(200, r#"{"id": 1, "name": "Aphex Twin"}"#))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let cache = Arc::new(MemoryCache::new(10));
+    cached_client(&server.uri(), cache.clone())
+        .artist(1)
+        .await
+        .unwrap();
+    DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .personal_token("testtoken")
+        .base_url(server.uri())
+        .cache(cache.clone())
+        .build()
+        .unwrap()
+        .artist(1)
+        .await
+        .unwrap();
+    assert_eq!(cache.len(), 2);
+}
+
+#[tokio::test]
+async fn write_invalidates_cached_resource() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/releases/1/rating/rodneyfool"))
+        .respond_with(json(
+            200,
+            r#"{"username": "rodneyfool", "release_id": 1, "rating": 3}"#,
+        ))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/releases/1/rating/rodneyfool"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let cache = Arc::new(MemoryCache::new(10));
+    let client = DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .personal_token("testtoken")
+        .base_url(server.uri())
+        .cache(cache.clone())
+        .build()
+        .unwrap();
+    client.user_release_rating(1, "rodneyfool").await.unwrap();
+    client
+        .delete_user_release_rating(1, "rodneyfool")
+        .await
+        .unwrap();
+    assert!(cache.is_empty());
+    client.user_release_rating(1, "rodneyfool").await.unwrap();
+}
+
+#[tokio::test]
+async fn write_invalidates_parent_resources_after_success() {
+    let server = MockServer::start().await;
+    for (release, title) in [("/releases/1", "One"), ("/releases/10", "Ten")] {
+        Mock::given(method("GET"))
+            .and(path(release))
+            .respond_with(json(200, &format!(r#"{{"id": 1, "title": "{title}"}}"#)))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/releases/1/rating"))
+        .respond_with(json(
+            200,
+            r#"{"rating": {"count": 45, "average": 3.42}, "release_id": 1}"#,
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/releases/1/rating/rodneyfool"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/releases/1/rating/rodneyfool"))
+        .respond_with(json(
+            201,
+            r#"{"username": "rodneyfool", "release_id": 1, "rating": 5}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let cache = Arc::new(MemoryCache::new(10));
+    let client = DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .personal_token("testtoken")
+        .base_url(server.uri())
+        .cache(cache.clone())
+        .build()
+        .unwrap();
+    client.release(1).await.unwrap();
+    client
+        .release_with_currency(1, Currency::Eur)
+        .await
+        .unwrap();
+    client.release(10).await.unwrap();
+    client.release_rating(1).await.unwrap();
+    assert_eq!(cache.len(), 4);
+
+    // A failed write changes nothing, so the cache is kept.
+    assert!(client
+        .put_user_release_rating(1, "rodneyfool", 5)
+        .await
+        .is_err());
+    assert_eq!(cache.len(), 4);
+
+    client
+        .put_user_release_rating(1, "rodneyfool", 5)
+        .await
+        .unwrap();
+    assert_eq!(cache.len(), 1);
+    client.release(10).await.unwrap();
+    assert_eq!(server.received_requests().await.unwrap().len(), 6);
+}
+
+// --- error handling ---
+
+#[tokio::test]
+async fn rate_limited_response_returns_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artists/1"))
+        .respond_with(ResponseTemplate::new(429))
+        .mount(&server)
+        .await;
+
+    let err = client(&server.uri()).artist(1).await.unwrap_err();
+    assert!(matches!(err, DiscogsError::RateLimited));
+}
+
+#[tokio::test]
+async fn api_error_response_captures_status_and_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artists/999"))
+        .respond_with(json(404, r#"{"message": "Release not found."}"#))
+        .mount(&server)
+        .await;
+
+    let err = client(&server.uri()).artist(999).await.unwrap_err();
+    match err {
+        DiscogsError::Api { status, body } => {
+            assert_eq!(status, 404);
+            assert!(body.contains("not found"));
+        }
+        other => panic!("expected Api error, got {other:?}"),
+    }
+}
+
+// --- retries ---
+
+fn retry_client(base_url: &str) -> DiscogsClient {
+    DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .base_url(base_url)
+        .retry_policy(
+            RetryPolicy::new()
+                .base_delay(Duration::from_millis(1))
+                .jitter(false),
+        )
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn server_error_is_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artists/1"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/artists/1"))
+        .respond_with(json(200, r#"{"id": 1, "name": "Aphex Twin"}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let artist = retry_client(&server.uri()).artist(1).await.unwrap();
+    assert_eq!(artist.name, "Aphex Twin");
+}
+
+#[tokio::test]
+async fn rate_limited_response_honours_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artists/1"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "0")
+                .insert_header("X-Discogs-Ratelimit", "600")
+                .insert_header("X-Discogs-Ratelimit-Used", "600"),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/artists/1"))
+        .respond_with(json(200, r#"{"id": 1, "name": "Aphex Twin"}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let artist = retry_client(&server.uri()).artist(1).await.unwrap();
+    assert_eq!(artist.name, "Aphex Twin");
+}
+
+#[tokio::test]
+async fn retries_give_up_after_max_attempts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artists/1"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("X-Discogs-Ratelimit", "600")
+                .insert_header("X-Discogs-Ratelimit-Used", "600"),
+        )
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let err = retry_client(&server.uri()).artist(1).await.unwrap_err();
+    assert!(matches!(err, DiscogsError::RateLimited));
+}
+
+#[tokio::test]
+async fn client_error_is_not_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/artists/999"))
+        .respond_with(json(404, r#"{"message": "Artist not found."}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = retry_client(&server.uri()).artist(999).await.unwrap_err();
+    assert!(matches!(err, DiscogsError::Api { status: 404, .. }));
+}
+
+#[tokio::test]
+async fn inventory_export_is_not_retried_after_server_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/inventory/export"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .personal_token("testtoken")
+        .base_url(server.uri())
+        .retry_policy(RetryPolicy::new().base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+    let err = client.request_inventory_export().await.unwrap_err();
+    assert!(matches!(err, DiscogsError::Api { status: 503, .. }));
+}
+
+#[tokio::test]
+async fn inventory_export_is_retried_after_rate_limit() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/inventory/export"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    mount_export_request(&server).await;
+
+    let client = DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .personal_token("testtoken")
+        .base_url(server.uri())
+        .retry_policy(RetryPolicy::new().base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+    assert_eq!(client.request_inventory_export().await.unwrap(), 42);
+}
+
+#[tokio::test]
+async fn create_listing_is_not_retried_after_server_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/marketplace/listings"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .personal_token("testtoken")
+        .base_url(server.uri())
+        .retry_policy(RetryPolicy::new().base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+    let params = ListingParams::new(249504, Condition::VeryGoodPlus, 12.5);
+    let err = client.create_listing(&params).await.unwrap_err();
+    assert!(matches!(err, DiscogsError::Api { status: 503, .. }));
+}
+
+#[tokio::test]
+async fn download_image_is_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/images/cover.jpg"))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/images/cover.jpg"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"JPEGDATA".to_vec()))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/images/cover.jpg", server.uri());
+    let bytes = retry_client(&server.uri())
+        .download_image(&url)
+        .await
+        .unwrap();
+    assert_eq!(&bytes[..], b"JPEGDATA");
+}
+
+// --- download_image / release_cover_art ---
+
+#[tokio::test]
+async fn download_image_returns_bytes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/images/cover.jpg"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "image/jpeg")
+                .set_body_bytes(b"FAKEJPEGDATA".to_vec()),
+        )
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/images/cover.jpg", server.uri());
+    let bytes = client(&server.uri()).download_image(&url).await.unwrap();
+    assert_eq!(&bytes[..], b"FAKEJPEGDATA");
+}
+
+#[tokio::test]
+async fn release_cover_art_returns_none_when_no_images() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/releases/1"))
+        .respond_with(json(200, r#"{"id": 1, "title": "No Cover", "images": []}"#))
+        .mount(&server)
+        .await;
+
+    let result = client(&server.uri()).release_cover_art(1).await.unwrap();
+    assert!(result.is_none());
+}
+
+#[tokio::test]
+async fn release_cover_art_downloads_primary_image() {
+    let server = MockServer::start().await;
+
+    let release_body = format!(
+        r#"{{"id": 1, "title": "Has Cover", "images": [{{"type": "primary", "uri": "{}/images/cover.jpg", "width": 300, "height": 300}}]}}"#,
+        server.uri()
+    );
+
+    Mock::given(method("GET"))
+        .and(path("/releases/1"))
+        .respond_with(json(200, &release_body))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/images/cover.jpg"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "image/jpeg")
+                .set_body_bytes(b"JPEGDATA".to_vec()),
+        )
+        .mount(&server)
+        .await;
+
+    let art = client(&server.uri())
+        .release_cover_art(1)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(&art.data[..], b"JPEGDATA");
+    assert_eq!(art.width, Some(300));
+    assert_eq!(art.height, Some(300));
+}
+
+#[tokio::test]
+async fn download_image_is_cached() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/images/cover.jpg"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"JPEGDATA".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = cached_client(&server.uri(), Arc::new(MemoryCache::new(10)));
+    let url = format!("{}/images/cover.jpg", server.uri());
+    client.download_image(&url).await.unwrap();
+    let bytes = client.download_image(&url).await.unwrap();
+    assert_eq!(&bytes[..], b"JPEGDATA");
 }
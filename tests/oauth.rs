@@ -0,0 +1,229 @@
+use discogger::{DiscogsClient, DiscogsError, OAuthFlow, RequestToken};
+use wiremock::matchers::{header_regex, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn flow(base_url: &str) -> OAuthFlow {
+    OAuthFlow::builder()
+        .user_agent("test/1.0")
+        .consumer("ckey", "csecret")
+        .base_url(base_url)
+        .build()
+        .unwrap()
+}
+
+fn form(body: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "application/x-www-form-urlencoded")
+        .set_body_string(body)
+}
+
+#[test]
+fn builder_requires_user_agent() {
+    assert!(matches!(
+        OAuthFlow::builder().consumer("ckey", "csecret").build(),
+        Err(DiscogsError::Configuration(_))
+    ));
+}
+
+#[tokio::test]
+async fn request_token_signs_callback() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/oauth/request_token"))
+        .and(header_regex(
+            "Authorization",
+            r#"oauth_callback="http%3A%2F%2Flocalhost%2Fcb""#,
+        ))
+        .and(header_regex(
+            "Authorization",
+            r#"oauth_consumer_key="ckey""#,
+        ))
+        .respond_with(form(
+            "oauth_token=rtoken&oauth_token_secret=rsecret&oauth_callback_confirmed=true",
+        ))
+        .mount(&server)
+        .await;
+
+    let token = flow(&server.uri())
+        .request_token("http://localhost/cb")
+        .await
+        .unwrap();
+    assert_eq!(token.token, "rtoken");
+    assert_eq!(token.token_secret, "rsecret");
+    assert!(token.callback_confirmed);
+}
+
+#[tokio::test]
+async fn authorize_url_includes_request_token() {
+    let server = MockServer::start().await;
+    let token = RequestToken {
+        token: "rtoken".into(),
+        token_secret: "rsecret".into(),
+        callback_confirmed: true,
+    };
+    assert_eq!(
+        flow(&server.uri()).authorize_url(&token),
+        "https://www.discogs.com/oauth/authorize?oauth_token=rtoken"
+    );
+}
+
+#[tokio::test]
+async fn access_token_exchanges_verifier() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/access_token"))
+        .and(header_regex("Authorization", r#"oauth_token="rtoken""#))
+        .and(header_regex("Authorization", r#"oauth_verifier="v123""#))
+        .respond_with(form("oauth_token=atoken&oauth_token_secret=asecret"))
+        .mount(&server)
+        .await;
+
+    let request_token = RequestToken {
+        token: "rtoken".into(),
+        token_secret: "rsecret".into(),
+        callback_confirmed: true,
+    };
+    let credentials = flow(&server.uri())
+        .access_token(&request_token, "v123")
+        .await
+        .unwrap();
+    assert_eq!(credentials.consumer_key, "ckey");
+    assert_eq!(credentials.token, "atoken");
+    assert_eq!(credentials.token_secret, "asecret");
+
+    // Credentials feed straight into the client builder.
+    assert!(DiscogsClient::builder()
+        .user_agent("test/1.0")
+        .oauth_credentials(credentials)
+        .build()
+        .is_ok());
+}
+
+#[tokio::test]
+async fn access_token_rejected_returns_api_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/access_token"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("Invalid verifier."))
+        .mount(&server)
+        .await;
+
+    let request_token = RequestToken {
+        token: "rtoken".into(),
+        token_secret: "rsecret".into(),
+        callback_confirmed: true,
+    };
+    let err = flow(&server.uri())
+        .access_token(&request_token, "bad")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscogsError::Api { status: 401, .. }));
+}
+
+#[cfg(feature = "oauth-loopback")]
+mod loopback {
+    use super::*;
+    use discogger::{LoopbackCallback, LoopbackOptions};
+    use std::time::Duration;
+
+    fn request_token() -> RequestToken {
+        RequestToken {
+            token: "rtoken".into(),
+            token_secret: "rsecret".into(),
+            callback_confirmed: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn loopback_captures_verifier() {
+        let callback = LoopbackCallback::bind(&LoopbackOptions::new())
+            .await
+            .unwrap();
+        let url = format!(
+            "{}?oauth_token=rtoken&oauth_verifier=v123",
+            callback.callback_url()
+        );
+        let redirect = tokio::spawn(async move { reqwest::get(url).await.unwrap().status() });
+
+        let verifier = callback.wait_for_verifier(&request_token()).await.unwrap();
+        assert_eq!(verifier, "v123");
+        assert!(redirect.await.unwrap().is_success());
+    }
+
+    #[tokio::test]
+    async fn loopback_ignores_other_tokens() {
+        let callback = LoopbackCallback::bind(&LoopbackOptions::new())
+            .await
+            .unwrap();
+        let base = callback.callback_url().to_string();
+        let redirect = tokio::spawn(async move {
+            let stale = reqwest::get(format!("{base}?oauth_token=other&oauth_verifier=x"))
+                .await
+                .unwrap();
+            assert_eq!(stale.status(), 400);
+            reqwest::get(format!("{base}?oauth_token=rtoken&oauth_verifier=v123"))
+                .await
+                .unwrap();
+        });
+
+        let verifier = callback.wait_for_verifier(&request_token()).await.unwrap();
+        assert_eq!(verifier, "v123");
+        redirect.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn loopback_skips_idle_and_broken_connections() {
+        let options = LoopbackOptions::new().read_timeout(Duration::from_millis(50));
+        let callback = LoopbackCallback::bind(&options).await.unwrap();
+        let base = callback.callback_url().to_string();
+        let addr = base
+            .trim_start_matches("http://")
+            .split('/')
+            .next()
+            .unwrap()
+            .to_string();
+        let redirect = tokio::spawn(async move {
+            // A preconnect that never sends a request, and one that hangs up.
+            let idle = tokio::net::TcpStream::connect(&addr).await.unwrap();
+            drop(tokio::net::TcpStream::connect(&addr).await.unwrap());
+            let response = reqwest::get(format!("{base}?oauth_token=rtoken&oauth_verifier=v123"))
+                .await
+                .unwrap();
+            drop(idle);
+            response.status()
+        });
+
+        let verifier = callback.wait_for_verifier(&request_token()).await.unwrap();
+        assert_eq!(verifier, "v123");
+        assert!(redirect.await.unwrap().is_success());
+    }
+
+    #[tokio::test]
+    async fn authorize_with_loopback_times_out() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/oauth/request_token"))
+            .and(header_regex(
+                "Authorization",
+                r#"oauth_callback="http%3A%2F%2F127.0.0.1"#,
+            ))
+            .respond_with(form("oauth_token=rtoken&oauth_token_secret=rsecret"))
+            .mount(&server)
+            .await;
+
+        let mut opened = None;
+        let err = flow(&server.uri())
+            .authorize_with_loopback(
+                &LoopbackOptions::new().timeout(Duration::from_millis(50)),
+                |url| opened = Some(url.to_string()),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DiscogsError::OAuth(_)));
+        assert_eq!(
+            opened.as_deref(),
+            Some("https://www.discogs.com/oauth/authorize?oauth_token=rtoken")
+        );
+    }
+}